@@ -68,7 +68,8 @@ fn main() {
 
     let program_sender = Pubkey::find_program_sender().0;
 
-    let program_sender_wallet = Pubkey::find_program_sender_wallet(&wrapped_sol_mint).0;
+    let program_sender_wallet =
+        Pubkey::find_program_sender_wallet(&wrapped_sol_mint, &spl_token::ID).0;
 
     let create_wallet =
         spl_associated_token_account::instruction::create_associated_token_account_idempotent(
@@ -19,10 +19,14 @@
 
 #![allow(clippy::result_large_err)]
 
-use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{log::sol_log_compute_units, sysvar},
+};
 use debridge_solana_sdk::{
-    check_claiming, estimator,
-    sending::{SendIx, SendSubmissionParamsInput},
+    check_claiming,
+    program_sender::{ProgramSender, ProgramSenderPubkey},
+    sending::{bumps::SendBumps, SendAccounts, SendIx, SendSubmissionParamsInput},
 };
 
 declare_id!("5UaXbex7paiRDykrN2GaRPW7j7goEQ1ZWqQvUwnAfFTF");
@@ -40,19 +44,18 @@ pub mod debridge_invoke_example {
         FailedToEstimateExpenses,
     }
 
-    use anchor_lang::solana_program::{program, program_error::ProgramError};
-    use debridge_solana_sdk::{
-        prelude::*,
-        sending,
-        sending::{SEND_FROM_INDEX, SEND_FROM_WALLET_INDEX},
-    };
-    use spl_token::solana_program::system_instruction;
+    use anchor_lang::solana_program::program_error::ProgramError;
+    use debridge_solana_sdk::{prelude::*, sending};
 
     use super::*;
 
     /// Debridge protocol allows transfer liquidity from Solana to other supported chains
     /// To send some token to other supported chain use [`debridge_solana_sdk::sending::invoke_debridge_send`]
     ///
+    /// To get [`debridge_solana_sdk::sending::SendReceipt`] with id of created submission use
+    /// [`debridge_solana_sdk::sending::invoke_debridge_send_with_receipt`].
+    /// Store it to match the submission with its claim in target chain
+    ///
     /// To check if the network is supported you can use [`debridge_solana_sdk::sending::is_chain_supported`]
     pub fn send_via_debridge(
         ctx: Context<SendViaDebridge>,
@@ -61,7 +64,7 @@ pub mod debridge_invoke_example {
         receiver: Vec<u8>,
         is_use_asset_fee: bool,
     ) -> Result<()> {
-        sending::invoke_debridge_send(
+        let receipt = sending::invoke_debridge_send_with_receipt(
             SendIx {
                 target_chain_id,
                 receiver,
@@ -71,8 +74,15 @@ pub mod debridge_invoke_example {
                 referral_code: None,
             },
             ctx.remaining_accounts,
-        )
-        .map_err(|err| err.into())
+        )?;
+
+        msg!(
+            "Submission id: {:?}, nonce: {}",
+            receipt.submission_id,
+            receipt.nonce
+        );
+
+        Ok(())
     }
 
     /// Debridge protocol takes fix fee and transfer fee while sending liquidity.
@@ -176,12 +186,19 @@ pub mod debridge_invoke_example {
             ErrorCode::FailedToCalculateAmountWithFee
         })?;
 
+        let chain_address_len =
+            sending::get_chain_address_len(ctx.remaining_accounts, target_chain_id)
+                .map_err(|_| ErrorCode::ChainNotSupported)?;
+
         let send_ix = SendIx {
             target_chain_id,
             receiver,
             is_use_asset_fee,
             amount: final_amount,
-            submission_params: Some(SendSubmissionParamsInput::execution_fee_only(execution_fee)),
+            submission_params: Some(SendSubmissionParamsInput::execution_fee_only(
+                execution_fee,
+                chain_address_len,
+            )),
             referral_code: None,
         };
 
@@ -200,12 +217,19 @@ pub mod debridge_invoke_example {
         receiver: Vec<u8>,
         execution_fee: u64,
     ) -> Result<()> {
+        let chain_address_len =
+            sending::get_chain_address_len(ctx.remaining_accounts, target_chain_id)
+                .map_err(|_| ErrorCode::ChainNotSupported)?;
+
         let send_ix = SendIx {
             target_chain_id,
             receiver,
             is_use_asset_fee: false,
             amount,
-            submission_params: Some(SendSubmissionParamsInput::execution_fee_only(execution_fee)),
+            submission_params: Some(SendSubmissionParamsInput::execution_fee_only(
+                execution_fee,
+                chain_address_len,
+            )),
             referral_code: None,
         };
 
@@ -218,9 +242,9 @@ pub mod debridge_invoke_example {
     /// Used `external_call` for this. For evm-like network it will be address of smart contract function and function's arguments
     /// packed in byte vector.
     ///
-    /// To use external call function needed to initialize external call storage with
-    /// [`debridge_solana_sdk::sending::invoke_init_external_call`] function and create `submission_params`
-    /// with [`debridge_solana_sdk::sending::SendSubmissionParamsInput::with_external_call`] function.
+    /// To send tokens with external call use [`debridge_solana_sdk::sending::invoke_send_with_external_call`]
+    /// function. This function will initialize external call storage, add all fees to `amount` so that
+    /// exactly `amount` is received in target chain and send tokens with external call `submission_params`.
     /// Besides external call needed to provide `fallback_address`. The `fallback_address' will be used
     /// if external call fails. On this address token received in target chain will transfer.
     ///
@@ -237,26 +261,20 @@ pub mod debridge_invoke_example {
         flags: [u8; 32],
         external_call: Vec<u8>,
     ) -> Result<()> {
-        debridge_sending::invoke_init_external_call(
-            external_call.as_slice(),
-            ctx.remaining_accounts,
-        )?;
-
-        let send_ix = SendIx {
+        sending::invoke_send_with_external_call(
+            external_call,
             target_chain_id,
             receiver,
-            is_use_asset_fee: false,
             amount,
-            submission_params: Some(SendSubmissionParamsInput::with_external_call(
-                external_call,
-                execution_fee,
-                fallback_address,
-                flags,
-            )),
-            referral_code: None,
-        };
+            execution_fee,
+            fallback_address,
+            flags,
+            None,
+            ctx.remaining_accounts,
+        )
+        .map_err(ProgramError::from)?;
 
-        sending::invoke_debridge_send(send_ix, ctx.remaining_accounts).map_err(|err| err.into())
+        Ok(())
     }
 
     /// deBridge protocol allows calling any smart contract in target chain without sending any tokens.
@@ -289,6 +307,7 @@ pub mod debridge_invoke_example {
             receiver,
             execution_fee,
             fallback_address,
+            None,
             ctx.remaining_accounts,
         )
         .map_err(ProgramError::from)?;
@@ -300,10 +319,11 @@ pub mod debridge_invoke_example {
     /// smart contract. For this, solana uses PDA accounts. With the help of pda accounts,
     /// the program ensures that it initiated the call to the send function of the debridge program
     ///
-    /// To use this feature, you need to use [`debridge_solana_sdk::sending::set_send_from_account`]
-    /// and provide PDA account and wallet that belongs to this account. Then you have to use debrige
-    /// sdk function with `_signed` postfix. In this example we use [`debridge_solana_sdk::sending::invoke_send_message_signed`].
-    /// These functions additionally need to pass signers seeds and bump.
+    /// To use this feature, you need to create [`debridge_solana_sdk::program_sender::ProgramSender`]
+    /// from PDA account, wallet that belongs to this account and signer seeds with bump.
+    /// It funds the PDA from the user and invokes debridge with the PDA as send_from.
+    /// Under the hood it uses [`debridge_solana_sdk::sending::set_send_from_account`] and
+    /// [`debridge_solana_sdk::sending::invoke_send_message_signed`].
     pub fn send_message_via_debridge_with_program_sender<'info>(
         ctx: Context<'_, '_, '_, 'info, SendViaDebridgeWithSender<'info>>,
         target_chain_id: [u8; 32],
@@ -312,67 +332,87 @@ pub mod debridge_invoke_example {
         fallback_address: Vec<u8>,
         message: Vec<u8>,
     ) -> Result<()> {
-        program::invoke(
-            &system_instruction::transfer(
-                ctx.remaining_accounts[SEND_FROM_INDEX].key,
-                ctx.accounts.program_sender.key,
-                estimator::get_native_sender_lamports_expenses(
-                    sending::get_chain_native_fix_fee(ctx.remaining_accounts, target_chain_id)
-                        .map_err(|_| ErrorCode::FailedToCalculateAmountWithFee)?,
-                    message.len(),
-                )
-                .map_err(|_| ErrorCode::FailedToEstimateExpenses)?,
-            ),
-            &[
-                ctx.remaining_accounts[SEND_FROM_INDEX].clone(),
-                ctx.accounts.program_sender.clone(),
-            ],
-        )?;
-
-        program::invoke(
-            &spl_token::instruction::transfer(
-                &spl_token::ID,
-                ctx.remaining_accounts[SEND_FROM_WALLET_INDEX].key,
-                ctx.accounts.program_sender_wallet.key,
-                ctx.remaining_accounts[SEND_FROM_INDEX].key,
-                &[],
-                sending::add_all_fees(
-                    ctx.remaining_accounts,
-                    target_chain_id,
-                    0,
-                    execution_fee,
-                    false,
-                )
-                .map_err(|_| ErrorCode::FailedToCalculateAmountWithFee)?,
-            )?,
-            &[
-                ctx.remaining_accounts[SEND_FROM_WALLET_INDEX].clone(),
-                ctx.accounts.program_sender_wallet.clone(),
-                ctx.remaining_accounts[SEND_FROM_INDEX].clone(),
-            ],
-        )?;
-
-        let mut accounts = ctx.remaining_accounts.to_vec();
+        let bump = *ctx.bumps.get("program_sender").expect("Failed to get bump");
+        let bump = &[bump];
+        let signer_seeds: &[&[u8]] = &[PROGRAM_SENDER_SEED, bump];
 
-        sending::set_send_from_account(
-            accounts.as_mut_slice(),
+        let program_sender = ProgramSender::new(
+            &ID,
             ctx.accounts.program_sender.clone(),
             ctx.accounts.program_sender_wallet.clone(),
-        );
+            signer_seeds,
+        )
+        .map_err(ProgramError::from)?;
 
-        let bump = *ctx.bumps.get("program_sender").expect("Failed to get bump");
+        program_sender
+            .fund_for_message(
+                ctx.remaining_accounts,
+                target_chain_id,
+                execution_fee,
+                message.len(),
+            )
+            .map_err(ProgramError::from)?;
 
-        sending::invoke_send_message_signed(
-            message,
+        program_sender
+            .invoke_send_message(
+                message,
+                target_chain_id,
+                receiver,
+                execution_fee,
+                fallback_address,
+                None,
+                ctx.remaining_accounts,
+            )
+            .map_err(ProgramError::from)?;
+
+        Ok(())
+    }
+
+    /// Fee helpers check that accounts of the send context are PDAs of expected seeds.
+    /// With stored bumps the checks use `create_program_address` instead of `find_program_address`.
+    /// To find bumps use [`debridge_solana_sdk::sending::bumps::SendBumps::find`] and to use them in
+    /// lookups use [`debridge_solana_sdk::sending::SendAccounts::with_bumps`]
+    ///
+    /// The instruction logs remaining compute units around every helper call with
+    /// [`anchor_lang::solana_program::log::sol_log_compute_units`]. Simulate it with
+    /// `ts-examples/examples/measureComputeUnits.ts` to get units consumed by helpers
+    /// with and without stored bumps
+    pub fn measure_fee_helpers(
+        ctx: Context<SendViaDebridge>,
+        amount: u64,
+        target_chain_id: [u8; 32],
+    ) -> Result<()> {
+        let accounts = SendAccounts::new(ctx.remaining_accounts)
+            .map_err(|_| ErrorCode::NotEnoughAccountProvided)?;
+        let bumps = SendBumps::find(
+            accounts.token_mint().key,
+            accounts.token_program().key,
             target_chain_id,
-            receiver,
-            execution_fee,
-            fallback_address,
-            accounts.as_slice(),
-            &[&[PROGRAM_SENDER_SEED, &[bump]]],
+            accounts.send_from().key,
+            [0; 32],
         )
         .map_err(ProgramError::from)?;
 
+        for (label, accounts) in [
+            ("without bumps", accounts),
+            ("with bumps", accounts.with_bumps(bumps)),
+        ] {
+            msg!("Measure fee helpers {}", label);
+            sol_log_compute_units();
+            accounts
+                .get_transfer_fee(target_chain_id)
+                .map_err(ProgramError::from)?;
+            sol_log_compute_units();
+            accounts
+                .get_chain_native_fix_fee(target_chain_id)
+                .map_err(ProgramError::from)?;
+            sol_log_compute_units();
+            accounts
+                .add_all_fees(target_chain_id, amount, 0, false)
+                .map_err(ProgramError::from)?;
+            sol_log_compute_units();
+        }
+
         Ok(())
     }
 
@@ -438,20 +478,17 @@ pub struct SendViaDebridgeWithSender<'info> {
 
 pub trait FindProgramSender {
     fn find_program_sender() -> (Pubkey, u8) {
-        Pubkey::find_program_address(&[PROGRAM_SENDER_SEED], &ID)
+        Pubkey::find_program_sender_address(PROGRAM_SENDER_SEED, &ID)
     }
 }
 impl FindProgramSender for Pubkey {}
 
 pub trait FindProgramSenderWallet {
-    fn find_program_sender_wallet(token_mint: &Pubkey) -> (Pubkey, u8) {
-        Pubkey::find_program_address(
-            &[
-                Pubkey::find_program_sender().0.as_ref(),
-                spl_token::ID.as_ref(),
-                token_mint.as_ref(),
-            ],
-            &spl_associated_token_account::ID,
+    fn find_program_sender_wallet(token_mint: &Pubkey, token_program: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_sender_wallet_address(
+            &Pubkey::find_program_sender().0,
+            token_mint,
+            token_program,
         )
     }
 }
@@ -273,6 +273,33 @@ export type DebridgeInvokeExample = {
         },
       ];
     },
+    {
+      name: "measureFeeHelpers";
+      docs: [
+        "Fee helpers check that accounts of the send context are PDAs of expected seeds.";
+        "With stored bumps the checks use `create_program_address` instead of `find_program_address`.";
+        "To find bumps use [`debridge_solana_sdk::sending::bumps::SendBumps::find`] and to use them in";
+        "lookups use [`debridge_solana_sdk::sending::SendAccounts::with_bumps`]";
+        "";
+        "The instruction logs remaining compute units around every helper call with";
+        "[`anchor_lang::solana_program::log::sol_log_compute_units`]. Simulate it with";
+        "`ts-examples/examples/measureComputeUnits.ts` to get units consumed by helpers";
+        "with and without stored bumps";
+      ];
+      accounts: [];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        },
+        {
+          name: "targetChainId";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+      ];
+    },
     {
       name: "checkClaiming";
       docs: [
@@ -620,6 +647,33 @@ export const IDL: DebridgeInvokeExample = {
         },
       ],
     },
+    {
+      name: "measureFeeHelpers",
+      docs: [
+        "Fee helpers check that accounts of the send context are PDAs of expected seeds.",
+        "With stored bumps the checks use `create_program_address` instead of `find_program_address`.",
+        "To find bumps use [`debridge_solana_sdk::sending::bumps::SendBumps::find`] and to use them in",
+        "lookups use [`debridge_solana_sdk::sending::SendAccounts::with_bumps`]",
+        "",
+        "The instruction logs remaining compute units around every helper call with",
+        "[`anchor_lang::solana_program::log::sol_log_compute_units`]. Simulate it with",
+        "`ts-examples/examples/measureComputeUnits.ts` to get units consumed by helpers",
+        "with and without stored bumps",
+      ],
+      accounts: [],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+        {
+          name: "targetChainId",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+      ],
+    },
     {
       name: "checkClaiming",
       docs: [
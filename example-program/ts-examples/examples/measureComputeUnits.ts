@@ -0,0 +1,59 @@
+/* eslint-disable no-console */
+import { crypto } from "@debridge-finance/solana-utils";
+
+import { buildSendContext } from "./contextBuilding";
+import { getDefaultArgs, initAll } from "./helpers";
+
+const HELPERS = ["get_transfer_fee", "get_chain_native_fix_fee", "add_all_fees"];
+
+/**
+ * Simulates `measureFeeHelpers` instruction and prints compute units consumed by every fee helper.
+ * Consumed units include 100 CU of one `sol_log_compute_units` call
+ */
+async function main() {
+  const args = getDefaultArgs();
+  const { connection, wallet, example, deBridge } = initAll();
+  const builder = example.methods.measureFeeHelpers(
+    args.amount,
+    Array.from(crypto.normalizeChainId(args.targetChain)),
+  );
+  const remainingAccounts = await buildSendContext(
+    deBridge,
+    wallet.publicKey,
+    args.tokenMint,
+    args.targetChain,
+    args.receiver,
+    false,
+    args.mode,
+  );
+  builder.remainingAccounts(remainingAccounts);
+
+  const tx = await builder.transaction();
+  tx.feePayer = wallet.publicKey;
+  tx.recentBlockhash = (await connection.getLatestBlockhash("finalized")).blockhash;
+  const { value } = await connection.simulateTransaction(tx);
+  if (value.err !== null) {
+    console.log(value.logs);
+    throw new Error(`Simulation failed: ${JSON.stringify(value.err)}`);
+  }
+
+  let label = "";
+  let remaining: number[] = [];
+  for (const log of value.logs ?? []) {
+    const measure = log.match(/Measure fee helpers (.+)$/);
+    if (measure !== null) {
+      [, label] = measure;
+      remaining = [];
+    }
+    const consumption = log.match(/Program consumption: (\d+) units remaining/);
+    if (consumption !== null) {
+      remaining.push(Number(consumption[1]));
+      if (remaining.length > 1) {
+        const helper = HELPERS[remaining.length - 2];
+        console.log(`${helper} ${label}: ${remaining[remaining.length - 2] - remaining[remaining.length - 1]} CU`);
+      }
+    }
+  }
+}
+
+main().catch(console.error);
@@ -106,7 +106,7 @@ impl ValidatedExecuteExtCallIx {
     ///
     /// * `submission` - A reference to the account info of the submission account to validate.
     /// * `validators` - The `SubmissionAccountValidation` object containing the expected values
-    ///                  for each field in the submission account.
+    ///   for each field in the submission account.
     ///
     /// # Errors
     ///
@@ -0,0 +1,435 @@
+/*
+ * Copyright (C) 2023 debridge
+ *
+ * This file is part of debridge-solana-sdk.
+ *
+ * debridge-solana-sdk is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Lesser General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * debridge-solana-sdk is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with debridge-solana-sdk. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::{collections::HashMap, convert::Infallible};
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    system_program,
+};
+
+use crate::{
+    debridge_accounts::{AssetFeeInfo, ExternalCallMeta, State, TryFromAccountData},
+    keys::{
+        AssetFeeInfoPubkey, AssociatedTokenPubkey, BridgePubkey, ChainSupportInfoPubkey,
+        DiscountInfoPubkey, ExternalCallMetaPubkey, ExternalCallStoragePubkey, MintAuthorityPubkey,
+        NonceStoragePubkey, StatePubkey,
+    },
+    sending::{close_external_call_instruction, SEND_META_TEMPLATE},
+    token::is_token_program_id,
+    Error, Pubkey, DEBRIDGE_ID, SETTINGS_ID,
+};
+
+/// Source of on-chain accounts data for building the send context outside of the program.
+/// Implement it over your rpc client or accounts cache.
+pub trait AccountFetcher {
+    type Error;
+
+    /// Get data of account by pubkey. `None` if account doesn't exist
+    fn get_account_data(&self, pubkey: &Pubkey) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Get owner of account by pubkey. It's used to detect token program of mint.
+    /// `None` if account doesn't exist
+    fn get_account_owner(&self, pubkey: &Pubkey) -> Result<Option<Pubkey>, Self::Error>;
+}
+
+/// Accounts cache of owner and data by pubkey
+impl AccountFetcher for HashMap<Pubkey, (Pubkey, Vec<u8>)> {
+    type Error = Infallible;
+
+    fn get_account_data(&self, pubkey: &Pubkey) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.get(pubkey).map(|(_, data)| data.clone()))
+    }
+
+    fn get_account_owner(&self, pubkey: &Pubkey) -> Result<Option<Pubkey>, Self::Error> {
+        Ok(self.get(pubkey).map(|(owner, _)| *owner))
+    }
+}
+
+/// Error of building send context outside of the program
+#[derive(Debug, thiserror::Error)]
+pub enum ResolveError<E> {
+    /// Fetched accounts don't form a valid send context
+    #[error("{0}")]
+    SdkError(#[from] Error),
+    /// [`AccountFetcher`] failed to provide account
+    #[error("Failed to fetch account: {0}")]
+    FetchError(E),
+}
+
+/// Build accounts for debridge send instruction. The result has the same
+/// order as expected by [`crate::sending::invoke_debridge_send`]
+///
+/// Discount and asset fee accounts are replaced by their default accounts
+/// if they don't exist for `sender` and `target_chain_id`
+///
+/// Token program and wallets are resolved for the owner of `token_mint`
+/// provided by [`AccountFetcher::get_account_owner`], so Token-2022 mints are supported.
+/// Fails with [`Error::WrongTokenMint`] if `token_mint` doesn't exist
+///
+/// # Arguments
+/// * `fetcher` - source of on-chain accounts data
+/// * `sender` - sender of tokens, it's used as send-from and external call storage owner
+/// * `token_mint` - mint of sending tokens
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `external_call_shortcut` - keccak256 hash of external call buffer
+pub fn resolve_send_accounts<F: AccountFetcher>(
+    fetcher: &F,
+    sender: &Pubkey,
+    token_mint: &Pubkey,
+    target_chain_id: [u8; 32],
+    external_call_shortcut: [u8; 32],
+) -> Result<Vec<AccountMeta>, ResolveError<F::Error>> {
+    let get_data = |pubkey: &Pubkey| {
+        fetcher
+            .get_account_data(pubkey)
+            .map_err(ResolveError::FetchError)
+    };
+
+    let token_program = fetcher
+        .get_account_owner(token_mint)
+        .map_err(ResolveError::FetchError)?
+        .ok_or(Error::WrongTokenMint)?;
+    if !is_token_program_id(&token_program) {
+        return Err(Error::WrongTokenProgramId.into());
+    }
+
+    let bridge = Pubkey::find_bridge_address(token_mint).0;
+    let mint_authority = Pubkey::find_mint_authority_address(&bridge).0;
+    let state = Pubkey::find_state_address().0;
+    let fee_beneficiary = get_data(&state)?
+        .ok_or(Error::WrongState)
+        .and_then(|data| State::try_from_account_data(&data))?
+        .fee_beneficiary;
+    let external_call_storage =
+        Pubkey::find_external_call_storage_address(&external_call_shortcut, sender).0;
+
+    let discount = match Pubkey::find_discount_info_address(sender).0 {
+        discount if get_data(&discount)?.is_some() => discount,
+        _ => Pubkey::find_no_discount_address().0,
+    };
+
+    let asset_fee = match Pubkey::find_asset_fee_info_address(&bridge, &target_chain_id)?.0 {
+        asset_fee
+            if get_data(&asset_fee)?
+                .map(|data| AssetFeeInfo::try_from_account_data(&data).is_ok())
+                .unwrap_or(false) =>
+        {
+            asset_fee
+        }
+        _ => Pubkey::default_bridge_fee_address()?.0,
+    };
+
+    let pubkeys = [
+        bridge,
+        *token_mint,
+        Pubkey::find_associated_token_address_with_program_id(
+            &mint_authority,
+            token_mint,
+            &token_program,
+        )
+        .0,
+        mint_authority,
+        Pubkey::find_chain_support_info_address(&target_chain_id)?.0,
+        SETTINGS_ID,
+        token_program,
+        state,
+        fee_beneficiary,
+        Pubkey::find_nonce_storage_address().0,
+        Pubkey::find_associated_token_address_with_program_id(sender, token_mint, &token_program).0,
+        system_program::ID,
+        external_call_storage,
+        Pubkey::find_external_call_meta_address(&external_call_storage).0,
+        *sender,
+        discount,
+        asset_fee,
+        DEBRIDGE_ID,
+    ];
+
+    Ok(pubkeys
+        .into_iter()
+        .zip(SEND_META_TEMPLATE)
+        .map(|(pubkey, meta)| AccountMeta {
+            pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect())
+}
+
+/// External call storage which rent can be reclaimed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReclaimableExternalCall {
+    /// Owner of external call storage. Rent is returned to it
+    pub send_from: Pubkey,
+    /// Keccak256 hash of external call
+    pub external_call_shortcut: [u8; 32],
+    /// External call storage of `send_from` for `external_call_shortcut`
+    pub external_call_storage: Pubkey,
+    /// Meta of `external_call_storage` in transferred or executed state
+    pub external_call_meta: Pubkey,
+}
+
+impl ReclaimableExternalCall {
+    /// Build instruction closing external call storage and meta. `send_from` must sign it
+    pub fn close_instruction(&self) -> Result<Instruction, ProgramError> {
+        close_external_call_instruction(
+            self.external_call_shortcut,
+            &self.external_call_storage,
+            &self.external_call_meta,
+            &self.send_from,
+        )
+    }
+}
+
+/// Filter external call storages of `send_from` which rent can be reclaimed.
+/// Storage is reclaimable when its meta is transferred or executed
+///
+/// Storages are not enumerated on-chain, so shortcuts of external calls sent by
+/// `send_from` have to be kept by the caller. Shortcut is keccak256 hash of external call,
+/// `SolanaKeccak256::hash(&external_call)`. On-chain it's returned as
+/// [`SendReceipt::external_call_shortcut`](crate::sending::SendReceipt::external_call_shortcut)
+/// by sends with receipt, so programs can store it in their state. Storages of unknown
+/// shortcuts can be found off-chain in transactions of `send_from` to debridge program
+///
+/// # Arguments
+/// * `fetcher` - source of on-chain accounts data
+/// * `send_from` - owner of external call storages
+/// * `external_call_shortcuts` - keccak256 hashes of external calls sent by `send_from`
+pub fn filter_reclaimable_external_calls<F: AccountFetcher>(
+    fetcher: &F,
+    send_from: &Pubkey,
+    external_call_shortcuts: impl IntoIterator<Item = [u8; 32]>,
+) -> Result<Vec<ReclaimableExternalCall>, F::Error> {
+    let mut reclaimable = Vec::new();
+
+    for external_call_shortcut in external_call_shortcuts {
+        let external_call_storage =
+            Pubkey::find_external_call_storage_address(&external_call_shortcut, send_from).0;
+        let external_call_meta = Pubkey::find_external_call_meta_address(&external_call_storage).0;
+
+        let is_reclaimable = fetcher
+            .get_account_data(&external_call_meta)?
+            .and_then(|data| ExternalCallMeta::try_from_account_data(&data).ok())
+            .map(|meta| meta.is_reclaimable())
+            .unwrap_or(false);
+
+        if is_reclaimable {
+            reclaimable.push(ReclaimableExternalCall {
+                send_from: *send_from,
+                external_call_shortcut,
+                external_call_storage,
+                external_call_meta,
+            });
+        }
+    }
+
+    Ok(reclaimable)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        client::{filter_reclaimable_external_calls, resolve_send_accounts, ResolveError},
+        hash::HashAdapter,
+        keys::{
+            AssetFeeInfoPubkey, AssociatedTokenPubkey, BridgePubkey, DiscountInfoPubkey,
+            ExternalCallMetaPubkey, ExternalCallStoragePubkey, StatePubkey,
+        },
+        sending::{
+            ASSET_FEE_INDEX, DISCOUNT_INDEX, FEE_BENEFICIARY_INDEX, SEND_FROM_INDEX,
+            SEND_FROM_WALLET_INDEX, TOKEN_PROGRAM_INDEX,
+        },
+        Error, Pubkey, SolanaKeccak256, DEBRIDGE_ID, POLYGON_CHAIN_ID, SETTINGS_ID,
+        SPL_TOKEN_2022_ID, SPL_TOKEN_ID,
+    };
+
+    #[test]
+    fn resolve_send_accounts_test() {
+        let sender = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let fee_beneficiary = Pubkey::new_unique();
+
+        let mut state_data = vec![216, 146, 107, 94, 104, 75, 182, 177, 0];
+        state_data.extend([0; 64]);
+        state_data.extend(fee_beneficiary.to_bytes());
+        state_data.extend([0; 57]);
+
+        let mut accounts =
+            HashMap::from([(Pubkey::find_state_address().0, (DEBRIDGE_ID, state_data))]);
+
+        assert!(matches!(
+            resolve_send_accounts(
+                &accounts,
+                &sender,
+                &token_mint,
+                POLYGON_CHAIN_ID,
+                SolanaKeccak256::hash(&[]),
+            ),
+            Err(ResolveError::SdkError(Error::WrongTokenMint))
+        ));
+
+        accounts.insert(token_mint, (SPL_TOKEN_ID, vec![0; 82]));
+        let metas = resolve_send_accounts(
+            &accounts,
+            &sender,
+            &token_mint,
+            POLYGON_CHAIN_ID,
+            SolanaKeccak256::hash(&[]),
+        )
+        .expect("Failed to resolve accounts");
+
+        assert_eq!(metas.len(), 18);
+        assert_eq!(metas[FEE_BENEFICIARY_INDEX].pubkey, fee_beneficiary);
+        assert_eq!(metas[SEND_FROM_INDEX].pubkey, sender);
+        assert!(metas[SEND_FROM_INDEX].is_signer);
+        assert_eq!(
+            metas[DISCOUNT_INDEX].pubkey,
+            Pubkey::find_no_discount_address().0
+        );
+        assert_eq!(
+            metas[ASSET_FEE_INDEX].pubkey,
+            Pubkey::default_bridge_fee_address().unwrap().0
+        );
+        assert_eq!(metas[17].pubkey, DEBRIDGE_ID);
+
+        let discount = Pubkey::find_discount_info_address(&sender).0;
+        let asset_fee = Pubkey::find_asset_fee_info_address(
+            &Pubkey::find_bridge_address(&token_mint).0,
+            &POLYGON_CHAIN_ID,
+        )
+        .unwrap()
+        .0;
+        accounts.insert(discount, (SETTINGS_ID, vec![]));
+        accounts.insert(
+            asset_fee,
+            (
+                SETTINGS_ID,
+                vec![
+                    37, 184, 34, 110, 54, 84, 57, 85, 255, 1, 100, 0, 0, 0, 0, 0, 0, 0,
+                ],
+            ),
+        );
+
+        let metas = resolve_send_accounts(
+            &accounts,
+            &sender,
+            &token_mint,
+            POLYGON_CHAIN_ID,
+            SolanaKeccak256::hash(&[]),
+        )
+        .expect("Failed to resolve accounts");
+
+        assert_eq!(metas[DISCOUNT_INDEX].pubkey, discount);
+        assert_eq!(metas[ASSET_FEE_INDEX].pubkey, asset_fee);
+    }
+
+    #[test]
+    fn resolve_token_2022_send_accounts_test() {
+        let sender = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+
+        let mut state_data = vec![216, 146, 107, 94, 104, 75, 182, 177, 0];
+        state_data.extend([0; 153]);
+
+        let accounts = HashMap::from([
+            (Pubkey::find_state_address().0, (DEBRIDGE_ID, state_data)),
+            (token_mint, (SPL_TOKEN_2022_ID, vec![0; 82])),
+        ]);
+
+        let metas = resolve_send_accounts(
+            &accounts,
+            &sender,
+            &token_mint,
+            POLYGON_CHAIN_ID,
+            SolanaKeccak256::hash(&[]),
+        )
+        .expect("Failed to resolve accounts");
+
+        assert_eq!(metas[TOKEN_PROGRAM_INDEX].pubkey, SPL_TOKEN_2022_ID);
+        assert_eq!(
+            metas[SEND_FROM_WALLET_INDEX].pubkey,
+            Pubkey::find_associated_token_address_with_program_id(
+                &sender,
+                &token_mint,
+                &SPL_TOKEN_2022_ID
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn filter_reclaimable_external_calls_test() {
+        let send_from = Pubkey::new_unique();
+        let meta_address = |shortcut: &[u8; 32]| {
+            Pubkey::find_external_call_meta_address(
+                &Pubkey::find_external_call_storage_address(shortcut, &send_from).0,
+            )
+            .0
+        };
+        let meta_discriminator = [52, 154, 212, 31, 208, 203, 151, 253];
+
+        let accounts = HashMap::from([
+            (
+                meta_address(&[1; 32]),
+                (
+                    DEBRIDGE_ID,
+                    [meta_discriminator.as_slice(), &[2], &[0; 8]].concat(),
+                ),
+            ),
+            (
+                meta_address(&[2; 32]),
+                (
+                    DEBRIDGE_ID,
+                    [meta_discriminator.as_slice(), &[0], &[0; 8]].concat(),
+                ),
+            ),
+            (
+                meta_address(&[3; 32]),
+                (DEBRIDGE_ID, [meta_discriminator.as_slice(), &[3]].concat()),
+            ),
+        ]);
+
+        let reclaimable = filter_reclaimable_external_calls(
+            &accounts,
+            &send_from,
+            [[1; 32], [2; 32], [3; 32], [4; 32]],
+        )
+        .expect("Failed to filter reclaimable external calls");
+
+        assert_eq!(
+            reclaimable
+                .iter()
+                .map(|external_call| external_call.external_call_shortcut)
+                .collect::<Vec<_>>(),
+            vec![[1; 32], [3; 32]]
+        );
+        assert_eq!(reclaimable[0].external_call_meta, meta_address(&[1; 32]));
+
+        let ix = reclaimable[0]
+            .close_instruction()
+            .expect("Failed to build ix");
+        assert_eq!(ix.program_id, DEBRIDGE_ID);
+        assert_eq!(ix.accounts[2].pubkey, send_from);
+        assert!(ix.accounts[2].is_signer);
+    }
+}
@@ -25,6 +25,8 @@ use crate::{Error, Pubkey};
 pub const EXECUTE_EXTERNAL_CALL_DISCRIMINATOR: [u8; 8] = [160, 89, 229, 51, 157, 62, 217, 174];
 pub const SEND_DISCRIMINATOR: [u8; 8] = [102, 251, 20, 187, 65, 75, 12, 69];
 pub const INIT_EXTERNAL_CALL_DISCRIMINATOR: [u8; 8] = [82, 77, 58, 138, 145, 157, 41, 253];
+pub const UPDATE_EXTERNAL_CALL_DISCRIMINATOR: [u8; 8] = [38, 234, 80, 194, 91, 237, 107, 143];
+pub const CLOSE_EXTERNAL_CALL_DISCRIMINATOR: [u8; 8] = [86, 133, 188, 0, 81, 191, 155, 134];
 
 /// Base anchor trait for account-data binary prefix
 trait Discriminator {
@@ -37,16 +39,24 @@ pub trait TryFromAccount: Sized + BorshSerialize + BorshDeserialize {
     fn try_from_account(account_info: &AccountInfo) -> Result<Self, Self::Error>;
 }
 
-impl<ACCOUNT: Discriminator + Sized + BorshSerialize + BorshDeserialize> TryFromAccount
+/// Parse account structure from raw account data,
+/// e.g. received from rpc outside of the program
+pub trait TryFromAccountData: Sized + BorshSerialize + BorshDeserialize {
+    type Error;
+
+    fn try_from_account_data(data: &[u8]) -> Result<Self, Self::Error>;
+}
+
+impl<ACCOUNT: Discriminator + Sized + BorshSerialize + BorshDeserialize> TryFromAccountData
     for ACCOUNT
 {
     type Error = Error;
 
-    fn try_from_account(account_info: &AccountInfo) -> Result<Self, Self::Error> {
-        let borrow_data = account_info
-            .try_borrow_data()
-            .map_err(|_| Error::AccountBorrowFailing)?;
-        let (discriminator, mut data) = borrow_data.split_at(8);
+    fn try_from_account_data(data: &[u8]) -> Result<Self, Self::Error> {
+        if data.len() < 8 {
+            return Err(Error::WrongAccountDiscriminator);
+        }
+        let (discriminator, mut data) = data.split_at(8);
 
         if discriminator.ne(&Self::discriminator()) {
             return Err(Error::WrongAccountDiscriminator);
@@ -56,6 +66,20 @@ impl<ACCOUNT: Discriminator + Sized + BorshSerialize + BorshDeserialize> TryFrom
     }
 }
 
+impl<ACCOUNT: Discriminator + Sized + BorshSerialize + BorshDeserialize> TryFromAccount
+    for ACCOUNT
+{
+    type Error = Error;
+
+    fn try_from_account(account_info: &AccountInfo) -> Result<Self, Self::Error> {
+        let borrow_data = account_info
+            .try_borrow_data()
+            .map_err(|_| Error::AccountBorrowFailing)?;
+
+        Self::try_from_account_data(&borrow_data)
+    }
+}
+
 /// The existence of this account proves the fact that the transfer
 /// was made and it is confirmed on the network
 ///
@@ -156,6 +180,10 @@ pub struct State {
     pub global_transfer_fee_bps: u64,
 }
 
+impl State {
+    pub const SEED: &'static [u8] = b"DEBRIDGE_STATE";
+}
+
 const STATE_ACCOUNT_DISCRIMINATOR: [u8; 8] = [216, 146, 107, 94, 104, 75, 182, 177];
 impl Discriminator for State {
     fn discriminator() -> [u8; 8] {
@@ -183,6 +211,40 @@ impl Discriminator for AssetFeeInfo {
     }
 }
 
+/// Discount of fees for specific sender
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiscountInfo {
+    /// Discount of fixed fee in basis points (1/10000)
+    pub fix_bps: u64,
+    /// Discount of transfer fee in basis points (1/10000)
+    pub transfer_bps: u64,
+}
+
+impl DiscountInfo {
+    pub const SEED: &'static [u8] = b"DISCOUNT_INFO";
+    pub const NO_DISCOUNT_SEED: &'static [u8] = b"NO_DISCOUNT";
+}
+
+const DISCOUNT_INFO_DISCRIMINATOR: [u8; 8] = [65, 172, 32, 7, 173, 203, 143, 107];
+impl Discriminator for DiscountInfo {
+    fn discriminator() -> [u8; 8] {
+        DISCOUNT_INFO_DISCRIMINATOR
+    }
+}
+
+/// Counter of sends in debridge program. Current nonce is used by the next send
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NonceStorage {
+    pub nonce: u64,
+}
+
+const NONCE_STORAGE_DISCRIMINATOR: [u8; 8] = [116, 193, 37, 78, 49, 76, 253, 53];
+impl Discriminator for NonceStorage {
+    fn discriminator() -> [u8; 8] {
+        NONCE_STORAGE_DISCRIMINATOR
+    }
+}
+
 /// To make a transfer within debridge infrastructure,
 /// you need a bridge. This account represents the information
 /// we store for each bridge
@@ -243,6 +305,14 @@ pub enum ExternalCallMeta {
 
 impl ExternalCallMeta {
     pub const SPACE: usize = 40;
+
+    /// External call is not needed anymore and rent of its accounts can be reclaimed
+    pub fn is_reclaimable(&self) -> bool {
+        matches!(
+            self,
+            ExternalCallMeta::Transferred { .. } | ExternalCallMeta::Executed
+        )
+    }
 }
 
 const EXTERNAL_CALL_META_DISCRIMINATOR: [u8; 8] = [52, 154, 212, 31, 208, 203, 151, 253];
@@ -77,6 +77,74 @@ pub enum Error {
     WrongClaimParentNativeSender,
     #[error("Account submission auth validation failed. Either an invalid submission was submitted, or an attempt was made to execute an instruction related to a different submission id")]
     SubmissionAuthValidationFailed,
+    #[error("Provided token mint differs from expected one. Please create account list with debridge sdk")]
+    WrongTokenMint,
+    #[error(
+        "Provided bridge not derived from token mint. Please create account list with debridge sdk"
+    )]
+    WrongBridge,
+    #[error("Provided staking wallet is not mint authority wallet. Please create account list with debridge sdk")]
+    WrongStakingWallet,
+    #[error("Provided mint authority not derived from bridge. Please create account list with debridge sdk")]
+    WrongMintAuthority,
+    #[error("Provided wrong spl token program id")]
+    WrongTokenProgramId,
+    #[error("Provided fee beneficiary differs from one in state account. Please create account list with debridge sdk")]
+    WrongFeeBeneficiary,
+    #[error("Provided wrong nonce storage. Please create account list with debridge sdk")]
+    WrongNonceStorage,
+    #[error("Provided send-from wallet is not send-from token wallet. Please create account list with debridge sdk")]
+    WrongSendFromWallet,
+    #[error("Provided wrong system program id")]
+    WrongSystemProgramId,
+    #[error("Provided external call storage for other shortcut or sender. Please create account list with debridge sdk")]
+    WrongExternalCallStorage,
+    #[error("Provided external call meta for other external call storage. Please create account list with debridge sdk")]
+    WrongExternalCallMeta,
+    #[error("Provided send-from differs from expected sender")]
+    WrongSendFrom,
+    #[error(
+        "Provided discount account for other sender. Please create account list with debridge sdk"
+    )]
+    WrongDiscount,
+    #[error("Receiver address length differs from target chain address length")]
+    WrongReceiverAddressLength,
+    #[error("Fallback address length differs from target chain address length")]
+    WrongFallbackAddressLength,
+    #[error("deBridge protocol is paused")]
+    ProtocolPaused,
+    #[error("Bridge of sending token is paused")]
+    BridgePaused,
+    #[error("Sending amount exceeds bridge max amount")]
+    AmountExceedsBridgeMax,
+    #[error("Sending amount is not enough to cover fees")]
+    AmountTooSmallToCoverFees,
+    #[error("Failed to get clock")]
+    FailedToGetClock,
+    #[error("External call storage length doesn't match external call")]
+    ExternalCallLengthMismatch,
+    #[error("Uploaded part of external call storage doesn't match external call")]
+    ExternalCallPrefixMismatch,
+    #[error("External call storage data doesn't match external call")]
+    ExternalCallDataMismatch,
+    #[error("Native fix fee exceeds fee limit")]
+    NativeFixFeeExceedsLimit,
+    #[error("Transfer fee bps exceeds fee limit")]
+    TransferFeeExceedsLimit,
+    #[error("Asset fix fee exceeds fee limit")]
+    AssetFixFeeExceedsLimit,
+    #[error("Received amount is less than minimum received amount")]
+    ReceivedAmountBelowMinimum,
+    #[error("Lamports debited from send_from don't match fee quote")]
+    UnexpectedLamportsDebited,
+    #[error("Tokens debited from send_from wallet don't match fee quote")]
+    UnexpectedTokensDebited,
+    #[error("Send amount doesn't match fee quote amount")]
+    SendAmountMismatchesFeeQuote,
+    #[error("Discount bps exceeds bps denominator")]
+    DiscountBpsExceedsDenominator,
+    #[error("Wrapped SOL wallet can't be closed, it holds tokens before the send")]
+    WrappedSolWalletNotEmpty,
 }
 
 use solana_program::program_error::ProgramError;
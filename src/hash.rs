@@ -36,6 +36,6 @@ impl HashAdapter for SolanaKeccak256 {
 impl HashAdapter for sha3::Keccak256 {
     fn hash(input: &[u8]) -> [u8; 32] {
         use sha3::Digest;
-        sha3::Keccak256::digest(input).try_into().unwrap()
+        sha3::Keccak256::digest(input).into()
     }
 }
\ No newline at end of file
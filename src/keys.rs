@@ -20,10 +20,54 @@
 use solana_program::pubkey::ParsePubkeyError;
 
 use crate::{
-    debridge_accounts::{AssetFeeInfo, Bridge, ChainSupportInfo},
-    Error, Pubkey, DEBRIDGE_ID, SETTINGS_ID, SOLANA_CHAIN_ID,
+    debridge_accounts::{AssetFeeInfo, Bridge, ChainSupportInfo, DiscountInfo, State},
+    Error, Pubkey, ASSOCIATED_TOKEN_PROGRAM_ID, DEBRIDGE_ID, SETTINGS_ID, SOLANA_CHAIN_ID,
+    SPL_TOKEN_ID,
 };
 
+// Addresses of PDAs without variable seeds. Finding them on-chain costs compute units
+// on every call, so with hardcoded program keys of the `prod` feature they are hardcoded too.
+// With the `env` feature they are found once on the first access
+cfg_match::cfg_match! {
+    feature = "prod" => {
+        /// Address of the debridge-settings [`State`] account
+        pub static STATE_ADDRESS: Pubkey =
+            Pubkey::new_from_array(env_to_array::bs58_to_array!("CcjkxrCJvfXrmds78hwCnovkdmTgE12wqojiVLrtW1qn"));
+
+        /// Address of the debridge nonce storage account
+        pub static NONCE_STORAGE_ADDRESS: Pubkey =
+            Pubkey::new_from_array(env_to_array::bs58_to_array!("2LKQceMRwfJNZovtSbsHmfszDYM5kTZHajFry2nqD2pi"));
+
+        /// Address of the discount account used when sender has no discount
+        pub static NO_DISCOUNT_ADDRESS: Pubkey =
+            Pubkey::new_from_array(env_to_array::bs58_to_array!("4kQYWVy6Vu8YUXVp5BgQC12ZX1HLRUfkK3bLzBFFjnNW"));
+
+        /// Address of the default [`AssetFeeInfo`] account used when bridge has no own asset fee
+        pub static DEFAULT_BRIDGE_FEE_ADDRESS: Pubkey =
+            Pubkey::new_from_array(env_to_array::bs58_to_array!("APMGxdbtubfWLQUACsN2yv2pxkvAgWwuxBe8ohFYoB37"));
+    }
+    feature = "env" => {
+        lazy_static::lazy_static! {
+            /// Address of the debridge-settings [`State`] account
+            pub static ref STATE_ADDRESS: Pubkey = Pubkey::find_state_address().0;
+
+            /// Address of the debridge nonce storage account
+            pub static ref NONCE_STORAGE_ADDRESS: Pubkey = Pubkey::find_nonce_storage_address().0;
+
+            /// Address of the discount account used when sender has no discount
+            pub static ref NO_DISCOUNT_ADDRESS: Pubkey = Pubkey::find_no_discount_address().0;
+
+            /// Address of the default [`AssetFeeInfo`] account used when bridge has no own asset fee
+            pub static ref DEFAULT_BRIDGE_FEE_ADDRESS: Pubkey = Pubkey::find_program_address(
+                &[AssetFeeInfo::DEFAULT_ASSET_FEE_SEED],
+                &SETTINGS_ID,
+            )
+            .0;
+        }
+    }
+    _ => {}
+}
+
 /// This trait is responsible for finding the pubkey for the [`ChainSupportInfo`] account
 pub trait ChainSupportInfoPubkey {
     fn find_chain_support_info_address(chain_id: &[u8; 32]) -> Result<(Pubkey, u8), Error> {
@@ -93,29 +137,6 @@ pub trait BridgePubkey {
 }
 impl BridgePubkey for Pubkey {}
 
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
-
-    use solana_program::pubkey::Pubkey;
-
-    use crate::keys::ChainSupportInfoPubkey;
-
-    #[test]
-    fn find_chain_support_info_test() {
-        let target_chain_id = [
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 0, 137,
-        ];
-        assert_eq!(
-            Pubkey::find_chain_support_info_address(&target_chain_id)
-                .unwrap()
-                .0,
-            Pubkey::from_str("8L81QZBfwA6Xi9zd49fyUfMRWJBCAxiUxd6jGHPnu1BQ").unwrap()
-        );
-    }
-}
-
 pub trait ExternalCallStoragePubkey {
     fn find_external_call_storage_address(shortcut: &[u8; 32], owner: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(
@@ -172,4 +193,194 @@ pub trait ExternalCallMetaPubkey {
         .ok())
     }
 }
-impl ExternalCallMetaPubkey for Pubkey {}
\ No newline at end of file
+impl ExternalCallMetaPubkey for Pubkey {}
+
+/// This trait is responsible for finding the pubkey for the bridge mint authority account
+pub trait MintAuthorityPubkey {
+    fn find_mint_authority_address(bridge: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"BRIDGE_MINTER", bridge.as_ref()], &DEBRIDGE_ID)
+    }
+
+    fn create_mint_authority_address(bridge: &Pubkey, bump: u8) -> Result<Option<Pubkey>, Error> {
+        Ok(Pubkey::create_program_address(
+            &[b"BRIDGE_MINTER", bridge.as_ref(), &[bump]],
+            &DEBRIDGE_ID,
+        )
+        .ok())
+    }
+}
+impl MintAuthorityPubkey for Pubkey {}
+
+/// This trait is responsible for finding the pubkey for the [`State`] account
+pub trait StatePubkey {
+    fn find_state_address() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[State::SEED], &SETTINGS_ID)
+    }
+
+    fn create_state_address(bump: u8) -> Result<Option<Pubkey>, Error> {
+        Ok(Pubkey::create_program_address(&[State::SEED, &[bump]], &SETTINGS_ID).ok())
+    }
+}
+impl StatePubkey for Pubkey {}
+
+/// This trait is responsible for finding the pubkey for the nonce storage account
+pub trait NonceStoragePubkey {
+    fn find_nonce_storage_address() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"NONCE"], &DEBRIDGE_ID)
+    }
+
+    fn create_nonce_storage_address(bump: u8) -> Result<Option<Pubkey>, Error> {
+        Ok(Pubkey::create_program_address(&[b"NONCE", &[bump]], &DEBRIDGE_ID).ok())
+    }
+}
+impl NonceStoragePubkey for Pubkey {}
+
+/// This trait is responsible for finding the pubkey for the sender [`DiscountInfo`] account
+pub trait DiscountInfoPubkey {
+    fn find_discount_info_address(sender: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[DiscountInfo::SEED, sender.as_ref()], &SETTINGS_ID)
+    }
+
+    fn create_discount_info_address(sender: &Pubkey, bump: u8) -> Result<Option<Pubkey>, Error> {
+        Ok(Pubkey::create_program_address(
+            &[DiscountInfo::SEED, sender.as_ref(), &[bump]],
+            &SETTINGS_ID,
+        )
+        .ok())
+    }
+
+    /// Account used in send context when sender has no discount
+    fn find_no_discount_address() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[DiscountInfo::NO_DISCOUNT_SEED], &SETTINGS_ID)
+    }
+}
+impl DiscountInfoPubkey for Pubkey {}
+
+/// This trait is responsible for finding the associated token wallet of some owner
+pub trait AssociatedTokenPubkey {
+    fn find_associated_token_address(owner: &Pubkey, token_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_associated_token_address_with_program_id(owner, token_mint, &SPL_TOKEN_ID)
+    }
+
+    /// Associated token wallet of mint owned by `token_program`. Use it for Token-2022 mints
+    fn find_associated_token_address_with_program_id(
+        owner: &Pubkey,
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[owner.as_ref(), token_program.as_ref(), token_mint.as_ref()],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+    }
+
+    fn create_associated_token_address_with_program_id(
+        owner: &Pubkey,
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+        bump: u8,
+    ) -> Result<Option<Pubkey>, Error> {
+        Ok(Pubkey::create_program_address(
+            &[
+                owner.as_ref(),
+                token_program.as_ref(),
+                token_mint.as_ref(),
+                &[bump],
+            ],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .ok())
+    }
+}
+impl AssociatedTokenPubkey for Pubkey {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use solana_program::pubkey::Pubkey;
+
+    use crate::keys::{
+        AssetFeeInfoPubkey, AssociatedTokenPubkey, ChainSupportInfoPubkey, DiscountInfoPubkey,
+        MintAuthorityPubkey, NonceStoragePubkey, StatePubkey, DEFAULT_BRIDGE_FEE_ADDRESS,
+        NONCE_STORAGE_ADDRESS, NO_DISCOUNT_ADDRESS, STATE_ADDRESS,
+    };
+
+    #[test]
+    fn find_chain_support_info_test() {
+        let target_chain_id = [
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 137,
+        ];
+        assert_eq!(
+            Pubkey::find_chain_support_info_address(&target_chain_id)
+                .unwrap()
+                .0,
+            Pubkey::from_str("8L81QZBfwA6Xi9zd49fyUfMRWJBCAxiUxd6jGHPnu1BQ").unwrap()
+        );
+    }
+
+    #[test]
+    fn find_fixed_send_accounts_test() {
+        assert_eq!(
+            Pubkey::find_state_address().0,
+            Pubkey::from_str("CcjkxrCJvfXrmds78hwCnovkdmTgE12wqojiVLrtW1qn").unwrap()
+        );
+        assert_eq!(
+            Pubkey::find_nonce_storage_address().0,
+            Pubkey::from_str("2LKQceMRwfJNZovtSbsHmfszDYM5kTZHajFry2nqD2pi").unwrap()
+        );
+        assert_eq!(
+            Pubkey::find_no_discount_address().0,
+            Pubkey::from_str("4kQYWVy6Vu8YUXVp5BgQC12ZX1HLRUfkK3bLzBFFjnNW").unwrap()
+        );
+        assert_eq!(
+            Pubkey::default_bridge_fee_address().unwrap().0,
+            Pubkey::from_str("APMGxdbtubfWLQUACsN2yv2pxkvAgWwuxBe8ohFYoB37").unwrap()
+        );
+    }
+
+    #[test]
+    fn fixed_send_accounts_addresses_test() {
+        assert!(STATE_ADDRESS.eq(&Pubkey::find_state_address().0));
+        assert!(NONCE_STORAGE_ADDRESS.eq(&Pubkey::find_nonce_storage_address().0));
+        assert!(NO_DISCOUNT_ADDRESS.eq(&Pubkey::find_no_discount_address().0));
+        assert!(DEFAULT_BRIDGE_FEE_ADDRESS.eq(&Pubkey::default_bridge_fee_address().unwrap().0));
+    }
+
+    #[test]
+    fn find_mint_authority_test() {
+        assert_eq!(
+            Pubkey::find_mint_authority_address(
+                &Pubkey::from_str("6SW1N9Rq2TqT3uQCD4F5zwtTTSFSarZmfyrk829SzsBX").unwrap()
+            )
+            .0,
+            Pubkey::from_str("7FmGdfJfDrrM6P68y7jijjj4xU9rH3hsUK2Kyp54iJUx").unwrap()
+        );
+    }
+
+    #[test]
+    fn find_associated_token_address_test() {
+        assert_eq!(
+            Pubkey::find_associated_token_address(
+                &Pubkey::from_str("7FmGdfJfDrrM6P68y7jijjj4xU9rH3hsUK2Kyp54iJUx").unwrap(),
+                &Pubkey::from_str("So11111111111111111111111111111111111111112").unwrap(),
+            )
+            .0,
+            Pubkey::from_str("8gjgVkHXTttCoSGGtzucFkJUWujQ8pgWuvnHCLSN7i3o").unwrap()
+        );
+
+        let owner = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let (wallet, bump) = Pubkey::find_associated_token_address(&owner, &token_mint);
+        assert_eq!(
+            Pubkey::create_associated_token_address_with_program_id(
+                &owner,
+                &token_mint,
+                &crate::SPL_TOKEN_ID,
+                bump
+            ),
+            Ok(Some(wallet))
+        );
+    }
+}
@@ -50,6 +50,20 @@ pub mod chain_ids;
 mod errors;
 
 pub mod estimator;
+
+/// This module is responsible for working with mints of spl-token
+/// and Token-2022 programs, e.g. Token-2022 transfer fee
+pub mod token;
+
+/// This module is responsible for sending on behalf of the program
+/// with using its PDA as send_from
+pub mod program_sender;
+
+/// This module is responsible for building accounts of debridge
+/// instructions outside of the program, e.g. in backend services
+#[cfg(feature = "client")]
+pub mod client;
+
 /// This module is auxiliary in working with hash
 mod hash;
 
@@ -93,6 +107,24 @@ cfg_match::cfg_match! {
     _ => {}
 }
 
+/// Program of spl-token program
+/// This program is responsible for transferring tokens from send-from wallet
+pub const SPL_TOKEN_ID: Pubkey = Pubkey::new_from_array(env_to_array::bs58_to_array!(
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+));
+
+/// Program of Token-2022 program
+/// It's used instead of spl-token program for Token-2022 mints
+pub const SPL_TOKEN_2022_ID: Pubkey = Pubkey::new_from_array(env_to_array::bs58_to_array!(
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+));
+
+/// Program of spl-associated-token-account program
+/// It's used to find staking and send-from wallets
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = Pubkey::new_from_array(
+    env_to_array::bs58_to_array!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+);
+
 pub mod prelude {
     pub use super::{
         chain_ids, check_claiming as debridge_check_claiming, sending as debridge_sending,
@@ -102,6 +134,38 @@ pub mod prelude {
 
 pub const BPS_DENOMINATOR: u64 = 10000_u64;
 
+/// Referral code attached to every send without own referral code.
+/// Pass it via `DEBRIDGE_REFERRAL_CODE` env variable at build time
+pub const DEFAULT_REFERRAL_CODE: Option<u32> =
+    parse_referral_code(option_env!("DEBRIDGE_REFERRAL_CODE"));
+
+const fn parse_referral_code(code: Option<&str>) -> Option<u32> {
+    let code = match code {
+        Some(code) => code.as_bytes(),
+        None => return None,
+    };
+    assert!(!code.is_empty(), "DEBRIDGE_REFERRAL_CODE is empty");
+
+    let mut result: u32 = 0;
+    let mut index = 0;
+    while index < code.len() {
+        assert!(
+            code[index].is_ascii_digit(),
+            "DEBRIDGE_REFERRAL_CODE must be decimal u32"
+        );
+        result = match result.checked_mul(10) {
+            Some(result) => match result.checked_add((code[index] - b'0') as u32) {
+                Some(result) => result,
+                None => panic!("DEBRIDGE_REFERRAL_CODE overflows u32"),
+            },
+            None => panic!("DEBRIDGE_REFERRAL_CODE overflows u32"),
+        };
+        index += 1;
+    }
+
+    Some(result)
+}
+
 // Checking that multiple environments cannot be enabled at the same time
 macro_rules! assert_unique_feature {
     () => {};
@@ -113,4 +177,17 @@ macro_rules! assert_unique_feature {
         assert_unique_feature!($($rest),*);
     }
 }
-assert_unique_feature!("prod", "env");
\ No newline at end of file
+assert_unique_feature!("prod", "env");
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_referral_code;
+
+    #[test]
+    fn parse_referral_code_test() {
+        assert_eq!(parse_referral_code(None), None);
+        assert_eq!(parse_referral_code(Some("0")), Some(0));
+        assert_eq!(parse_referral_code(Some("2000")), Some(2000));
+        assert_eq!(parse_referral_code(Some("4294967295")), Some(u32::MAX));
+    }
+}
\ No newline at end of file
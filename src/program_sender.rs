@@ -0,0 +1,388 @@
+/*
+ * Copyright (C) 2023 debridge
+ *
+ * This file is part of debridge-solana-sdk.
+ *
+ * debridge-solana-sdk is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Lesser General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * debridge-solana-sdk is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with debridge-solana-sdk. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    system_instruction,
+};
+
+use crate::{
+    errors::InvokeError,
+    estimator,
+    keys::AssociatedTokenPubkey,
+    sending::{self, SendAccounts, SendIx},
+    token, Error, Pubkey,
+};
+
+const TRANSFER_CHECKED_TAG: u8 = 12;
+
+/// This trait is responsible for finding the pubkeys of program sender PDA and its token wallet
+pub trait ProgramSenderPubkey {
+    fn find_program_sender_address(seed: &[u8], program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[seed], program_id)
+    }
+
+    fn find_program_sender_wallet_address(
+        program_sender: &Pubkey,
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_associated_token_address_with_program_id(
+            program_sender,
+            token_mint,
+            token_program,
+        )
+    }
+}
+impl ProgramSenderPubkey for Pubkey {}
+
+/// PDA of the program used as send_from. Funds for the send are transferred
+/// to it from the user and the send is signed by the program
+pub struct ProgramSender<'a, 'info> {
+    program_sender: AccountInfo<'info>,
+    program_sender_wallet: AccountInfo<'info>,
+    signer_seeds: &'a [&'a [u8]],
+}
+
+impl<'a, 'info> ProgramSender<'a, 'info> {
+    /// Create program sender and check that it's PDA of `program_id` with `signer_seeds`
+    ///
+    /// # Arguments
+    /// * `program_id` - id of the program owning program sender
+    /// * `program_sender` - program sender PDA
+    /// * `program_sender_wallet` - token wallet of program sender
+    /// * `signer_seeds` - seeds of program sender PDA with bump
+    pub fn new(
+        program_id: &Pubkey,
+        program_sender: AccountInfo<'info>,
+        program_sender_wallet: AccountInfo<'info>,
+        signer_seeds: &'a [&'a [u8]],
+    ) -> Result<Self, Error> {
+        if Pubkey::create_program_address(signer_seeds, program_id)
+            .map(|address| address.ne(program_sender.key))
+            .unwrap_or(true)
+        {
+            return Err(Error::WrongSendFrom);
+        }
+
+        Ok(ProgramSender {
+            program_sender,
+            program_sender_wallet,
+            signer_seeds,
+        })
+    }
+
+    /// Program sender PDA used as send_from
+    pub fn program_sender(&self) -> &AccountInfo<'info> {
+        &self.program_sender
+    }
+
+    /// Token wallet of program sender used as send-from wallet
+    pub fn program_sender_wallet(&self) -> &AccountInfo<'info> {
+        &self.program_sender_wallet
+    }
+
+    /// Transfer lamports and tokens from send_from of `account_infos` to program sender
+    ///
+    /// # Arguments
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk with user as send_from
+    /// * `lamports` - lamports transferred to program sender
+    /// * `token_amount` - tokens transferred to program sender wallet
+    pub fn fund(
+        &self,
+        account_infos: &[AccountInfo<'info>],
+        lamports: u64,
+        token_amount: u64,
+    ) -> ProgramResult {
+        let accounts = SendAccounts::try_from(account_infos)?;
+
+        if lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    accounts.send_from().key,
+                    self.program_sender.key,
+                    lamports,
+                ),
+                &[
+                    accounts.send_from().clone(),
+                    self.program_sender.clone(),
+                    accounts.system_program().clone(),
+                ],
+            )?;
+        }
+
+        if token_amount > 0 {
+            invoke(
+                &transfer_checked_instruction(
+                    accounts.token_program().key,
+                    accounts.send_from_wallet().key,
+                    accounts.token_mint().key,
+                    self.program_sender_wallet.key,
+                    accounts.send_from().key,
+                    token_amount,
+                    token::get_mint_decimals(accounts.token_mint())?,
+                ),
+                &[
+                    accounts.send_from_wallet().clone(),
+                    accounts.token_mint().clone(),
+                    self.program_sender_wallet.clone(),
+                    accounts.send_from().clone(),
+                    accounts.token_program().clone(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Transfer from send_from to program sender lamports and tokens needed
+    /// to send message with [`ProgramSender::invoke_send_message`]. Token-2022
+    /// transfer fee of funding transfer is added to transferred tokens
+    ///
+    /// Fees are calculated for accounts with program sender as send_from,
+    /// the same accounts that are used by the send
+    ///
+    /// # Arguments
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk with user as send_from
+    /// * `target_chain_id` - chain id to which the message is sent
+    /// * `execution_fee` - amount of execution fee
+    /// * `external_call_len` - length of external call
+    pub fn fund_for_message(
+        &self,
+        account_infos: &[AccountInfo<'info>],
+        target_chain_id: [u8; 32],
+        execution_fee: u64,
+        external_call_len: usize,
+    ) -> Result<(), InvokeError> {
+        let send_account_infos = self.substitute_accounts(account_infos)?;
+        let send_accounts = SendAccounts::try_from(send_account_infos.as_slice())?;
+        let lamports = estimator::get_native_sender_lamports_expenses(
+            send_accounts.get_chain_native_fix_fee(target_chain_id)?,
+            external_call_len,
+        )?;
+        let token_amount = token::add_token_transfer_fee(
+            send_accounts.token_mint(),
+            send_accounts.add_all_fees(target_chain_id, 0, execution_fee, false)?,
+        )?;
+
+        self.fund(account_infos, lamports, token_amount)?;
+
+        Ok(())
+    }
+
+    /// Copy send accounts with program sender and its wallet as send_from and send-from wallet.
+    /// Fails with [`Error::WrongAccountIndex`] if not all accounts of the send context are provided
+    ///
+    /// # Arguments
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk
+    pub fn substitute_accounts(
+        &self,
+        account_infos: &[AccountInfo<'info>],
+    ) -> Result<Vec<AccountInfo<'info>>, Error> {
+        SendAccounts::try_from(account_infos)?;
+
+        let mut accounts = account_infos.to_vec();
+        sending::set_send_from_account(
+            accounts.as_mut_slice(),
+            self.program_sender.clone(),
+            self.program_sender_wallet.clone(),
+        )?;
+
+        Ok(accounts)
+    }
+
+    /// Invoke send instruction in debridge program on behalf of program sender
+    ///
+    /// # Arguments
+    /// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk
+    pub fn invoke_debridge_send(
+        &self,
+        send_ix: SendIx,
+        account_infos: &[AccountInfo<'info>],
+    ) -> ProgramResult {
+        sending::invoke_debridge_send_signed(
+            send_ix,
+            &self.substitute_accounts(account_infos)?,
+            &[self.signer_seeds],
+        )
+    }
+
+    /// Send message to other chain without liquidity on behalf of program sender
+    ///
+    /// # Arguments
+    /// * `external_call` - instructions sending in target chain
+    /// * `target_chain_id` - chain id to which the tokens are sent
+    /// * `receiver` - send message to other chain without liquidity.
+    /// * `execution_fee` - chain id to which the tokens are sent
+    /// * `fallback_address` - reserve address for sending tokens if external call fails
+    /// * `referral_code` - referral code of integrator. [`crate::DEFAULT_REFERRAL_CODE`] is used if `None`
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_message(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+        account_infos: &[AccountInfo<'info>],
+    ) -> Result<(), InvokeError> {
+        sending::invoke_send_message_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            execution_fee,
+            fallback_address,
+            referral_code,
+            &self.substitute_accounts(account_infos)?,
+            &[self.signer_seeds],
+        )
+    }
+}
+
+fn transfer_checked_instruction(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    token_mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *token_program,
+        &[
+            [TRANSFER_CHECKED_TAG].as_slice(),
+            &amount.to_le_bytes(),
+            &[decimals],
+        ]
+        .concat(),
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::account_info::AccountInfo;
+
+    use crate::{
+        program_sender::{transfer_checked_instruction, ProgramSender, ProgramSenderPubkey},
+        sending::{SEND_FROM_INDEX, SEND_FROM_WALLET_INDEX, SEND_META_TEMPLATE},
+        Error, Pubkey, SPL_TOKEN_2022_ID,
+    };
+
+    #[test]
+    fn test_transfer_checked_instruction() {
+        let (source, token_mint, destination, owner) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        let ix = transfer_checked_instruction(
+            &SPL_TOKEN_2022_ID,
+            &source,
+            &token_mint,
+            &destination,
+            &owner,
+            1000,
+            6,
+        );
+
+        assert_eq!(ix.program_id, SPL_TOKEN_2022_ID);
+        assert_eq!(ix.data, [12, 232, 3, 0, 0, 0, 0, 0, 0, 6]);
+        assert_eq!(ix.accounts[0].pubkey, source);
+        assert_eq!(ix.accounts[1].pubkey, token_mint);
+        assert!(!ix.accounts[1].is_writable);
+        assert!(ix.accounts[2].is_writable);
+        assert_eq!(ix.accounts[3].pubkey, owner);
+        assert!(ix.accounts[3].is_signer);
+    }
+
+    #[test]
+    fn test_program_sender() {
+        let program_id = Pubkey::new_unique();
+        let (program_sender_key, bump) =
+            Pubkey::find_program_sender_address(b"PROGRAM_SENDER", &program_id);
+        let program_sender_wallet_key = Pubkey::new_unique();
+
+        let keys = (0..SEND_META_TEMPLATE.len())
+            .map(|_| Pubkey::new_unique())
+            .chain([program_sender_key, program_sender_wallet_key])
+            .collect::<Vec<_>>();
+        let mut lamports = vec![0; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        let mut account_infos = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &program_id, false, 0)
+            })
+            .collect::<Vec<_>>();
+        let program_sender_wallet = account_infos.pop().unwrap();
+        let program_sender = account_infos.pop().unwrap();
+
+        let bump = [bump];
+        let signer_seeds: &[&[u8]] = &[b"PROGRAM_SENDER", &bump];
+
+        assert!(matches!(
+            ProgramSender::new(
+                &Pubkey::new_unique(),
+                program_sender.clone(),
+                program_sender_wallet.clone(),
+                signer_seeds,
+            ),
+            Err(Error::WrongSendFrom)
+        ));
+
+        let sender = ProgramSender::new(
+            &program_id,
+            program_sender,
+            program_sender_wallet,
+            signer_seeds,
+        )
+        .expect("Failed to create program sender");
+
+        assert!(matches!(
+            sender.substitute_accounts(&account_infos[..SEND_FROM_INDEX]),
+            Err(Error::WrongAccountIndex)
+        ));
+
+        let substituted = sender.substitute_accounts(&account_infos).unwrap();
+        assert_eq!(substituted.len(), account_infos.len());
+        assert_eq!(substituted[SEND_FROM_INDEX].key, &program_sender_key);
+        assert_eq!(
+            substituted[SEND_FROM_WALLET_INDEX].key,
+            &program_sender_wallet_key
+        );
+        assert_eq!(account_infos[0].key, substituted[0].key);
+    }
+}
@@ -17,67 +17,162 @@
  * along with debridge-solana-sdk. If not, see <https://www.gnu.org/licenses/>.
  */
 
+/// This module is responsible for lookups of send context accounts
+/// with stored bumps of PDA accounts
+pub mod bumps;
+/// This module is responsible for sending native SOL
+/// with wrapping it into wSOL in the same transaction
+pub mod native;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
     instruction::{AccountMeta, Instruction},
-    program::invoke_signed,
+    msg,
+    program::{get_return_data, invoke_signed},
     program_error::ProgramError,
+    system_program,
 };
 
 use crate::{
     debridge_accounts::{
-        AssetFeeInfo, ChainSupportInfo, ExternalCallMeta, State, TryFromAccount,
-        INIT_EXTERNAL_CALL_DISCRIMINATOR, SEND_DISCRIMINATOR,
+        AssetFeeInfo, Bridge, BridgeState, ChainSupportInfo, DiscountInfo, ExternalCallMeta,
+        NonceStorage, State, Status, TryFromAccount, CLOSE_EXTERNAL_CALL_DISCRIMINATOR,
+        INIT_EXTERNAL_CALL_DISCRIMINATOR, SEND_DISCRIMINATOR, UPDATE_EXTERNAL_CALL_DISCRIMINATOR,
     },
     errors::InvokeError,
-    flags::SetReservedFlag,
+    estimator,
+    flags::{CheckReservedFlag, SetReservedFlag},
     hash::HashAdapter,
-    keys::{AssetFeeInfoPubkey, BridgePubkey, ChainSupportInfoPubkey},
-    Error, Pubkey, SolanaKeccak256, BPS_DENOMINATOR, DEBRIDGE_ID, SOLANA_CHAIN_ID,
+    keys::{DEFAULT_BRIDGE_FEE_ADDRESS, NONCE_STORAGE_ADDRESS, NO_DISCOUNT_ADDRESS, STATE_ADDRESS},
+    sending::bumps::SendBumps,
+    token::{self, TransferFeeConfig},
+    Error, Pubkey, SolanaKeccak256, BPS_DENOMINATOR, DEBRIDGE_ID, DEFAULT_REFERRAL_CODE,
+    SETTINGS_ID, SOLANA_CHAIN_ID,
 };
 
+const OVERFLOW_ERR: Error = Error::AmountOverflowedWhileAddingFee;
+
 /// Struct for forming send instruction in debridge program
-#[derive(BorshSerialize, BorshDeserialize)]
+///
+/// Use [`SendIxBuilder`] to reject combinations of fields that debridge program rejects
+#[derive(BorshSerialize, BorshDeserialize, Clone, derive_builder::Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct SendIx {
     /// Chain id to which the tokens are sent
     pub target_chain_id: [u8; 32],
     /// Address in `target_chain_id` that will receive the transferred tokens
     pub receiver: Vec<u8>,
     /// Id of the network to which the tokens are sent
+    #[builder(default)]
     pub is_use_asset_fee: bool,
     /// Amount of sending tokens. From this amount fee will be taken
     pub amount: u64,
     /// Additional data for tokens sending with auto external execution
+    #[builder(default, setter(strip_option))]
     pub submission_params: Option<SendSubmissionParamsInput>,
-    /// Not used
+    /// Referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+    #[builder(default, setter(strip_option))]
     pub referral_code: Option<u32>,
 }
 
+impl SendIxBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if matches!(&self.receiver, Some(receiver) if receiver.is_empty()) {
+            return Err("Receiver is empty".to_string());
+        }
+
+        if let (Some(Some(params)), Some(receiver)) = (&self.submission_params, &self.receiver) {
+            if params.fallback_address.len() != receiver.len() {
+                return Err("Fallback address length differs from receiver length".to_string());
+            }
+            if matches!(self.amount, Some(amount) if params.execution_fee > amount) {
+                return Err("Execution fee exceeds amount".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SendIx {
+    /// Check send against live settings accounts: target chain is supported,
+    /// asset fee is available if it's used and addresses have length of target chain address
+    ///
+    /// # Arguments
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk
+    pub fn validate_against(self, account_infos: &[AccountInfo]) -> Result<Self, Error> {
+        if !is_chain_supported(account_infos, self.target_chain_id)? {
+            return Err(Error::TargetChainNotSupported);
+        }
+        if self.is_use_asset_fee && !is_asset_fee_available(account_infos, self.target_chain_id)? {
+            return Err(Error::AssetFeeNotSupported);
+        }
+        check_send_addresses_len(account_infos, &self)?;
+
+        Ok(self)
+    }
+}
+
 /// Option params for send instruction
-#[derive(BorshSerialize, BorshDeserialize)]
+///
+/// Use [`SendSubmissionParamsInputBuilder`] to reject combinations of fields that debridge program rejects
+#[derive(BorshSerialize, BorshDeserialize, Clone, derive_builder::Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct SendSubmissionParamsInput {
     /// Reward for execution claim transaction in target chain
+    #[builder(default)]
     pub execution_fee: u64,
     /// Flags for additional protocol features
+    #[builder(default)]
     pub flags: [u8; 32],
     /// Reserve address for sending tokens if external call fails
     pub fallback_address: Vec<u8>,
     /// Keccak256 hash of external call buffer
+    #[builder(default = "SolanaKeccak256::hash(&[])")]
     pub external_call_shortcut: [u8; 32],
 }
 
+impl SendSubmissionParamsInputBuilder {
+    /// Set keccak256 hash of `external_call` as external call shortcut
+    pub fn external_call(&mut self, external_call: &[u8]) -> &mut Self {
+        self.external_call_shortcut(SolanaKeccak256::hash(external_call))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if matches!(&self.fallback_address, Some(fallback_address) if fallback_address.is_empty()) {
+            return Err("Fallback address is empty".to_string());
+        }
+
+        let is_send_hashed_data =
+            matches!(&self.flags, Some(flags) if flags.check_send_hashed_data());
+        let has_external_call = matches!(
+            self.external_call_shortcut,
+            Some(shortcut) if shortcut.ne(&SolanaKeccak256::hash(&[]))
+        );
+        if is_send_hashed_data && !has_external_call {
+            return Err("Hashed data flag is set without external call".to_string());
+        }
+
+        Ok(())
+    }
+}
+
 impl SendSubmissionParamsInput {
     /// Create submission params for sending with execution fee and without external call
     ///
+    /// Fallback address is not used without external call, but it has to have
+    /// the length of target chain address. Use [`get_chain_address_len`] to get it.
+    ///
     /// # Arguments
     /// * `execution_fee` - amount of execution fee
-    pub fn execution_fee_only(execution_fee: u64) -> Self {
+    /// * `chain_address_len` - length of address in target chain
+    pub fn execution_fee_only(execution_fee: u64, chain_address_len: u16) -> Self {
         SendSubmissionParamsInput {
             execution_fee,
             flags: [0; 32],
-            fallback_address: vec![0; 20],
+            fallback_address: vec![0; chain_address_len.into()],
             external_call_shortcut: SolanaKeccak256::hash(&[]),
         }
     }
@@ -103,6 +198,31 @@ impl SendSubmissionParamsInput {
         }
     }
 
+    /// Create submission params for external call sent only as its keccak256 hash.
+    /// External call is not stored in Solana, executor provides it in target chain.
+    /// [`flags::SEND_HASHED_DATA`](crate::flags::SEND_HASHED_DATA) flag is set
+    ///
+    /// # Arguments
+    /// * `external_call_shortcut` - keccak256 hash of external call
+    /// * `execution_fee` - amount of execution fee
+    /// * `fallback_address` -  reserve address for sending tokens if external call fails
+    /// * `flags` - flags for additional debridge protocol features
+    pub fn with_hashed_external_call(
+        external_call_shortcut: [u8; 32],
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        mut flags: [u8; 32],
+    ) -> Self {
+        flags.set_send_hashed_data();
+
+        SendSubmissionParamsInput {
+            execution_fee,
+            flags,
+            fallback_address,
+            external_call_shortcut,
+        }
+    }
+
     /// Create submission params for sending message to other chain
     ///
     /// This path is the default path for sending an authorised message
@@ -133,6 +253,19 @@ impl SendSubmissionParamsInput {
     }
 }
 
+/// Information about submission created by send
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SendReceipt {
+    /// Id of created submission. Use it to match confirmations, refunds and responses.
+    /// Taken from debridge return data if it's set, otherwise calculated with
+    /// [`calculate_submission_id`]. `None` if it can't be calculated for the sending token
+    pub submission_id: Option<[u8; 32]>,
+    /// Nonce used by the send, read from nonce storage before the send
+    pub nonce: u64,
+    /// Keccak256 hash of external call. Hash of empty buffer if send is without external call
+    pub external_call_shortcut: [u8; 32],
+}
+
 /// Invoke send instruction in debridge program
 ///
 /// # Arguments
@@ -154,543 +287,3369 @@ pub fn invoke_debridge_send_signed(
     account_infos: &[AccountInfo],
     signers_seeds: &[&[&[u8]]],
 ) -> ProgramResult {
-    if account_infos.len() < SEND_META_TEMPLATE.len() {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    }
-
-    if account_infos[SEND_META_TEMPLATE.len() - 1]
-        .key
-        .ne(&DEBRIDGE_ID)
-    {
-        return Err(Error::WrongDebridgeProgramId.into());
-    }
-
-    let ix = Instruction {
-        program_id: DEBRIDGE_ID,
-        accounts: account_infos
-            .iter()
-            .take(SEND_META_TEMPLATE.len())
-            .zip(SEND_META_TEMPLATE)
-            .map(|(acc, meta)| AccountMeta {
-                pubkey: *acc.key,
-                is_signer: meta.is_signer,
-                is_writable: meta.is_writable,
-            })
-            .collect(),
-        data: [
-            SEND_DISCRIMINATOR.as_slice(),
-            send_ix.try_to_vec()?.as_slice(),
-        ]
-        .concat(),
-    };
-
-    invoke_signed(&ix, account_infos, signers_seeds)
-}
-
-/// Struct for forming send instruction in debridge program
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct InitExternalCallIx {
-    /// Len of external call array
-    pub external_call_len: u32,
-    /// Target chain id
-    pub chain_id: [u8; 32],
-    /// Keccak hash of external call
-    pub external_call_shortcut: [u8; 32],
-    /// Message that send and try to execute in target chain
-    pub external_call: Vec<u8>,
+    SendAccounts::unchecked(account_infos).invoke_debridge_send_signed(send_ix, signers_seeds)
 }
 
-/// Create account for storing external call buffer
+/// Invoke send instruction in debridge program and return [`SendReceipt`] of created submission
 ///
-/// In the case of a large external call it can be called
-/// by a separate transaction in conjunction with
-/// `update_external_call` method from TS client
+/// Nonce storage is read before the send and debridge return data after it,
+/// so it costs more compute units than [`invoke_debridge_send`]
 ///
 /// # Arguments
-/// * `external_call` - instructions sending in target chain
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-pub fn invoke_init_external_call(
-    external_call: &[u8],
+pub fn invoke_debridge_send_with_receipt(
+    send_ix: SendIx,
     account_infos: &[AccountInfo],
-) -> Result<(), ProgramError> {
-    invoke_init_external_call_signed(external_call, account_infos, &[])
+) -> Result<SendReceipt, ProgramError> {
+    invoke_debridge_send_with_receipt_signed(send_ix, account_infos, &[])
 }
 
-/// Create account for storing external call buffer with using PDA account as send_from.
+/// Invoke send instruction in debridge program with using PDA account as send_from
+/// and return [`SendReceipt`] of created submission.
 /// You can using this function for sending message on behalf of the program.
 ///
 /// # Arguments
-/// * `external_call` - instructions sending in target chain
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
 /// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
-pub fn invoke_init_external_call_signed(
-    external_call: &[u8],
+pub fn invoke_debridge_send_with_receipt_signed(
+    send_ix: SendIx,
     account_infos: &[AccountInfo],
     signers_seeds: &[&[&[u8]]],
-) -> Result<(), ProgramError> {
-    let external_call_storage = account_infos[EXTERNAL_CALL_STORAGE_INDEX].clone();
-    let external_call_meta = account_infos[EXTERNAL_CALL_META_INDEX].clone();
-    let send_from = account_infos[SEND_FROM_INDEX].clone();
-    let system_program = account_infos[SYSTEM_PROGRAM_INDEX].clone();
-    let debridge_program = account_infos[DEBRIDGE_PROGRAM_INDEX].clone();
-
-    if external_call_meta.owner.eq(&DEBRIDGE_ID) {
-        return match ExternalCallMeta::try_from_account(&external_call_meta)? {
-            ExternalCallMeta::Transferred { .. } => Ok(()),
-            _ => Err(InvokeError::SdkError(Error::ExternalStorageWrongState).into()),
-        };
-    }
+) -> Result<SendReceipt, ProgramError> {
+    SendAccounts::unchecked(account_infos)
+        .invoke_debridge_send_with_receipt_signed(send_ix, signers_seeds)
+}
 
-    let accounts = vec![
-        AccountMeta {
-            pubkey: *external_call_storage.key,
-            is_signer: false,
-            is_writable: true,
-        },
-        AccountMeta {
-            pubkey: *external_call_meta.key,
-            is_signer: false,
-            is_writable: true,
-        },
-        AccountMeta {
-            pubkey: *send_from.key,
-            is_signer: true,
-            is_writable: true,
-        },
-        AccountMeta {
-            pubkey: *system_program.key,
-            is_signer: false,
-            is_writable: false,
-        },
-    ];
+/// Layout of debridge send return data isn't documented. It's expected to be submission id
+/// followed by little-endian nonce and is used only if the nonce matches nonce storage
+/// read before the send, otherwise submission id is calculated
+fn parse_send_return_data(data: &[u8]) -> Option<([u8; 32], u64)> {
+    let submission_id = data.get(..32)?.try_into().ok()?;
+    let nonce = u64::from_le_bytes(data.get(32..40)?.try_into().ok()?);
 
-    invoke_signed(
-        &Instruction::new_with_bytes(
-            DEBRIDGE_ID,
-            &[
-                INIT_EXTERNAL_CALL_DISCRIMINATOR.as_slice(),
-                InitExternalCallIx {
-                    external_call_len: external_call.len() as u32,
-                    chain_id: SOLANA_CHAIN_ID,
-                    external_call_shortcut: SolanaKeccak256::hash(external_call),
-                    external_call: external_call.to_vec(),
-                }
-                .try_to_vec()
-                .map_err(ProgramError::from)?
-                .as_slice(),
-            ]
-            .concat(),
-            accounts,
-        ),
-        &[
-            external_call_storage,
-            external_call_meta,
-            send_from,
-            system_program,
-            debridge_program,
-        ],
-        signers_seeds,
-    )?;
+    Some((submission_id, nonce))
+}
 
-    Ok(())
+fn get_external_call_shortcut(send_ix: &SendIx) -> [u8; 32] {
+    send_ix
+        .submission_params
+        .as_ref()
+        .map(|params| params.external_call_shortcut)
+        .unwrap_or_else(|| SolanaKeccak256::hash(&[]))
 }
 
-/// Send message to other chain without liquidity.
-/// Perform debridge send flow with zero amount
+/// Submission prefix of debridge gate, `uint256(1)`
+const SUBMISSION_PREFIX: [u8; 32] = u256_be_bytes(1);
+
+const fn u256_be_bytes(value: u64) -> [u8; 32] {
+    let value = value.to_be_bytes();
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    while i < value.len() {
+        bytes[24 + i] = value[i];
+        i += 1;
+    }
+    bytes
+}
+
+/// Calculate id of submission created by send with `nonce`. Nonce has to be read
+/// with [`get_nonce`] before the send
+///
+/// Id is calculated as `DeBridgeGate.getSubmissionIdFrom` of debridge-contracts-v1 does in
+/// target chain: keccak256 of packed submission prefix `1`, debridge id, Solana chain id,
+/// target chain id, received amount, receiver and nonce. If send has submission params,
+/// execution fee, flags, keccak256 of fallback address, external call shortcut and
+/// keccak256 of send_from are packed after them. Numbers are packed as 32-byte big-endian.
+///
+/// Debridge id is keccak256 of Solana chain id and token mint only for tokens native to Solana,
+/// so `None` is returned for tokens wrapped by debridge, minted by mint authority of the bridge
 ///
 /// # Arguments
-/// * `external_call` - instructions sending in target chain
-/// * `target_chain_id` - chain id to which the tokens are sent
-/// * `receiver` - send message to other chain without liquidity.
-/// * `execution_fee` - chain id to which the tokens are sent
-/// * `fallback_address` - reserve address for sending tokens if external call fails
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-pub fn invoke_send_message(
-    external_call: Vec<u8>,
-    target_chain_id: [u8; 32],
-    receiver: Vec<u8>,
-    execution_fee: u64,
-    fallback_address: Vec<u8>,
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `nonce` - nonce of the send
+pub fn calculate_submission_id(
     account_infos: &[AccountInfo],
-) -> Result<(), InvokeError> {
-    invoke_send_message_signed(
-        external_call,
-        target_chain_id,
-        receiver,
-        execution_fee,
-        fallback_address,
-        account_infos,
-        &[],
-    )
+    send_ix: &SendIx,
+    nonce: u64,
+) -> Result<Option<[u8; 32]>, Error> {
+    SendAccounts::unchecked(account_infos).calculate_submission_id(send_ix, nonce)
 }
 
-/// Send message to other chain without liquidity with using PDA account as send_from.
-/// You can using this function for sending message on behalf of the program.
-/// Perform debridge send flow with zero amount.
+/// Number of per-destination accounts of each send in [`invoke_debridge_send_batch`]
+pub const SEND_BATCH_GROUP_LEN: usize = 4;
+
+/// Invoke several send instructions in debridge program with the same token and sender
+///
+/// Accounts are the send accounts shared by all sends followed by [`SEND_BATCH_GROUP_LEN`]
+/// accounts of each send in order of `send_ixs`: chain support info, asset fee,
+/// external call storage and external call meta. The same accounts in the shared
+/// send accounts are not used, any accounts can be passed instead of them.
+///
+/// Accounts of all sends are checked against their target chains and external calls
+/// before the first send, so no send is invoked if any accounts don't match.
+/// Returns [`SendReceipt`] of each send in order of `send_ixs`
 ///
 /// # Arguments
-/// * `external_call` - instructions sending in target chain
-/// * `target_chain_id` - chain id to which the tokens are sent
-/// * `receiver` - send message to other chain without liquidity.
-/// * `execution_fee` - chain id to which the tokens are sent
-/// * `fallback_address` - reserve address for sending tokens if external call fails
-/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `send_ixs` - [`SendIx`] structures of sends
+/// * `account_infos` - shared send accounts followed by per-destination accounts
+pub fn invoke_debridge_send_batch(
+    send_ixs: &[SendIx],
+    account_infos: &[AccountInfo],
+) -> Result<Vec<SendReceipt>, ProgramError> {
+    invoke_debridge_send_batch_signed(send_ixs, account_infos, &[])
+}
+
+/// Invoke several send instructions in debridge program with using PDA account as send_from.
+/// Accounts are the same as in [`invoke_debridge_send_batch`]
+///
+/// # Arguments
+/// * `send_ixs` - [`SendIx`] structures of sends
+/// * `account_infos` - shared send accounts followed by per-destination accounts
 /// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
-pub fn invoke_send_message_signed(
-    external_call: Vec<u8>,
-    target_chain_id: [u8; 32],
-    receiver: Vec<u8>,
-    execution_fee: u64,
-    fallback_address: Vec<u8>,
+pub fn invoke_debridge_send_batch_signed(
+    send_ixs: &[SendIx],
     account_infos: &[AccountInfo],
     signers_seeds: &[&[&[u8]]],
-) -> Result<(), InvokeError> {
-    invoke_init_external_call_signed(external_call.as_slice(), account_infos, signers_seeds)?;
-
-    let send_ix = SendIx {
-        target_chain_id,
-        receiver,
-        is_use_asset_fee: false,
-        amount: add_all_fees(account_infos, target_chain_id, 0, execution_fee, false)
-            .map_err(|_| Error::AmountOverflowedWhileAddingFee)?,
-        submission_params: Some(SendSubmissionParamsInput::with_message(
-            external_call,
-            execution_fee,
-            fallback_address,
-        )),
-        referral_code: None,
-    };
-
-    invoke_debridge_send_signed(send_ix, account_infos, signers_seeds)?;
-
-    Ok(())
-}
+) -> Result<Vec<SendReceipt>, ProgramError> {
+    if account_infos.len() != SEND_META_TEMPLATE.len() + send_ixs.len() * SEND_BATCH_GROUP_LEN {
+        return Err(Error::WrongAccountIndex.into());
+    }
 
-#[cfg(test)]
-mod tests {
-    use borsh::BorshSerialize;
+    let (shared_account_infos, groups) = account_infos.split_at(SEND_META_TEMPLATE.len());
 
-    use crate::sending::{SendIx, SendSubmissionParamsInput, SEND_DISCRIMINATOR};
+    let sends_account_infos = send_ixs
+        .iter()
+        .zip(groups.chunks_exact(SEND_BATCH_GROUP_LEN))
+        .map(|(send_ix, group)| {
+            let mut send_account_infos = shared_account_infos.to_vec();
+            send_account_infos[CHAIN_SUPPORT_INFO_INDEX] = group[0].clone();
+            send_account_infos[ASSET_FEE_INDEX] = group[1].clone();
+            send_account_infos[EXTERNAL_CALL_STORAGE_INDEX] = group[2].clone();
+            send_account_infos[EXTERNAL_CALL_META_INDEX] = group[3].clone();
 
-    #[test]
-    fn test_send_ix_consistency() {
-        let send_ix = SendIx {
-            target_chain_id: [13; 32],
-            receiver: vec![14; 32],
-            is_use_asset_fee: false,
-            amount: 1000,
-            submission_params: Some(SendSubmissionParamsInput {
-                execution_fee: 100,
-                flags: [1; 32],
-                fallback_address: vec![15; 32],
-                external_call_shortcut: [16; 32],
-            }),
-            referral_code: Some(2000),
-        };
+            check_send_destination_accounts(&send_account_infos, send_ix)?;
+            Ok(send_account_infos)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
 
-        assert_eq!(
-            SEND_DISCRIMINATOR
-                .into_iter()
-                .chain(send_ix.try_to_vec().expect("Unreachable"))
-                .collect::<Vec<u8>>(),
-            vec![
-                102, 251, 20, 187, 65, 75, 12, 69, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13,
-                13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 32,
-                0, 0, 0, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14,
-                14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 0, 232, 3, 0, 0, 0, 0, 0,
-                0, 1, 100, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
-                1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 32, 0, 0, 0, 15, 15, 15, 15, 15, 15,
-                15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15,
-                15, 15, 15, 15, 15, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16,
-                16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 1, 208, 7, 0, 0
-            ]
-        )
-    }
+    send_ixs
+        .iter()
+        .zip(sends_account_infos)
+        .map(|(send_ix, send_account_infos)| {
+            invoke_debridge_send_with_receipt_signed(
+                send_ix.clone(),
+                &send_account_infos,
+                signers_seeds,
+            )
+        })
+        .collect()
 }
 
-/// Get State account structure from sending accounts
+/// Check that per-destination send accounts refer to target chain and external call of send:
+/// chain support info, asset fee, external call storage and external call meta
 ///
 /// # Arguments
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-pub fn get_state(account_infos: &[AccountInfo]) -> Result<State, Error> {
-    get_account_by_index(account_infos, STATE_INDEX)
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+pub fn check_send_destination_accounts(
+    account_infos: &[AccountInfo],
+    send_ix: &SendIx,
+) -> Result<(), Error> {
+    SendAccounts::unchecked(account_infos).check_send_destination_accounts(send_ix)
 }
 
-/// Get Chain Support info account  account structure from sending accounts
+/// Check that receiver and fallback address of send have length of target chain address
 ///
 /// # Arguments
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn get_chain_support_info(
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+pub fn check_send_addresses_len(
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<ChainSupportInfo, Error> {
-    check_chain_support_info_account(account_infos, target_chain_id)
-        .and_then(|()| get_account_by_index(account_infos, CHAIN_SUPPORT_INFO_INDEX))
+    send_ix: &SendIx,
+) -> Result<(), Error> {
+    SendAccounts::unchecked(account_infos).check_send_addresses_len(send_ix)
 }
 
-/// Check that provided chain support info account refers to `target_chain_id`
+/// Check that send will not be rejected by debridge program because of protocol status.
+/// Call it before [`invoke_debridge_send`] to handle these cases with your own errors
+/// instead of reverting the whole transaction inside debridge program
+///
+/// Checks that protocol and bridge are not paused, target chain is supported,
+/// receiver and fallback address lengths match target chain and amount doesn't exceed
+/// bridge max amount
 ///
 /// # Arguments
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent}
-pub fn check_chain_support_info_account(
-    account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<(), Error> {
-    account_infos
-        .get(CHAIN_SUPPORT_INFO_INDEX)
-        .ok_or(Error::WrongAccountIndex)
-        .and_then(|chain_support_info| {
-            Pubkey::find_chain_support_info_address(&target_chain_id)?
-                .0
-                .eq(chain_support_info.key)
-                .then_some(())
-                .ok_or(Error::WrongChainSupportInfo)
-        })
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+pub fn preflight_send(account_infos: &[AccountInfo], send_ix: &SendIx) -> Result<(), Error> {
+    SendAccounts::unchecked(account_infos).preflight_send(send_ix)
 }
 
-/// Get Bridge asset fee info account account structure from sending accounts
+/// Limits of send fees accepted by sender. Fill it from the quote shown to user
+/// to protect send from fee changes between quoting and execution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeLimits {
+    /// Max fixed fee in lamports. Checked if fee is paid in lamports
+    pub max_native_fix_fee: u64,
+    /// Max transfer fee in basis points with discount applied
+    pub max_transfer_fee_bps: u64,
+    /// Max fixed fee in sending tokens. Checked if fee is paid in sending tokens
+    pub max_asset_fix_fee: u64,
+    /// Min amount of tokens that will be received in target chain
+    pub min_received_amount: u64,
+}
+
+impl Default for FeeLimits {
+    /// No limits
+    fn default() -> Self {
+        FeeLimits {
+            max_native_fix_fee: u64::MAX,
+            max_transfer_fee_bps: u64::MAX,
+            max_asset_fix_fee: u64::MAX,
+            min_received_amount: 0,
+        }
+    }
+}
+
+/// Check that live fees of send don't exceed `fee_limits`
+/// and received amount is not less than minimum.
+/// Fees are compared with discount of sender applied, as debridge program takes them
 ///
 /// # Arguments
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn get_asset_fee_info(
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_limits` - fee limits accepted by sender
+pub fn check_fee_limits(
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<AssetFeeInfo, Error> {
-    account_infos
-        .get(TOKEN_MINT_INDEX)
-        .zip(account_infos.get(ASSET_FEE_INDEX))
-        .ok_or(Error::WrongAccountIndex)
-        .and_then(|(token_mint, asset_fee)| {
-            Pubkey::find_asset_fee_info_address(
-                &Pubkey::find_bridge_address(token_mint.key).0,
-                &target_chain_id,
-            )?
-            .0
-            .eq(asset_fee.key)
-            .then_some(())
-            .ok_or(Error::WrongBridgeFeeInfo)
-        })
-        .and_then(|()| get_account_by_index(account_infos, ASSET_FEE_INDEX))
+    send_ix: &SendIx,
+    fee_limits: &FeeLimits,
+) -> Result<(), Error> {
+    SendAccounts::unchecked(account_infos).check_fee_limits(send_ix, fee_limits)
 }
 
-/// Parse account structure from sending accounts by index
+/// Invoke send instruction in debridge program if live fees don't exceed `fee_limits`.
+/// Fees are checked with [`check_fee_limits`] before sending
 ///
 /// # Arguments
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_limits` - fee limits accepted by sender
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `account_index` - account index from sending accounts  
-pub fn get_account_by_index<T: TryFromAccount<Error = Error>>(
+pub fn invoke_debridge_send_with_fee_limits(
+    send_ix: SendIx,
+    fee_limits: &FeeLimits,
     account_infos: &[AccountInfo],
-    account_index: usize,
-) -> Result<T, Error> {
-    if account_infos.len() <= account_index {
-        return Err(Error::WrongAccountIndex);
-    }
-    T::try_from_account(&account_infos[account_index])
+) -> Result<SendReceipt, ProgramError> {
+    invoke_debridge_send_with_fee_limits_signed(send_ix, fee_limits, account_infos, &[])
 }
 
-/// Check the possibility of sending to the chain by chain id
+/// Invoke send instruction in debridge program if live fees don't exceed `fee_limits`
+/// with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
 ///
 /// # Arguments
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_limits` - fee limits accepted by sender
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn is_chain_supported(
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_debridge_send_with_fee_limits_signed(
+    send_ix: SendIx,
+    fee_limits: &FeeLimits,
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<bool, Error> {
-    Ok(
-        match get_chain_support_info(account_infos, target_chain_id)? {
-            ChainSupportInfo::Supported { .. } => true,
-            ChainSupportInfo::NotSupported => false,
-        },
+    signers_seeds: &[&[&[u8]]],
+) -> Result<SendReceipt, ProgramError> {
+    SendAccounts::unchecked(account_infos).invoke_debridge_send_with_fee_limits_signed(
+        send_ix,
+        fee_limits,
+        signers_seeds,
     )
 }
 
-/// Get transfer fee bps for sending current tokens to target chain id
+/// Invoke send instruction in debridge program and verify balances debited by debridge program.
+/// Lamports of send_from and tokens of send_from wallet are snapshotted before the CPI and
+/// compared with [`FeeQuote::native_fix_fee`] and [`FeeQuote::amount`] after it
+///
+/// `send_ix` amount has to be equal to [`FeeQuote::amount`]. Debridge program debits exactly
+/// `send_ix` amount of tokens, so the token delta only confirms that no extra tokens were taken.
+/// Only the lamport delta detects drift of fee settings since the quote
+///
+/// External call rent is not debited by send, so [`FeeQuote::external_call_rent`] isn't checked
 ///
 /// # Arguments
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_quote` - expected fees, e.g. from [`quote_send`]
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn get_transfer_fee(
+pub fn invoke_debridge_send_checked(
+    send_ix: SendIx,
+    fee_quote: &FeeQuote,
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<u64, Error> {
-    get_transfer_fee_for_chain(account_infos, target_chain_id).and_then(|chain_fee| {
-        chain_fee
-            .map(Ok)
-            .unwrap_or_else(|| Ok(get_state(account_infos)?.global_transfer_fee_bps))
-    })
+) -> Result<SendReceipt, ProgramError> {
+    invoke_debridge_send_checked_signed(send_ix, fee_quote, account_infos, &[])
 }
 
-/// Some networks have their own transfer fee bps
-/// Get own transfer fee bps to target chain id if defined
+/// Invoke send instruction in debridge program and verify balances debited by debridge program
+/// with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
 ///
 /// # Arguments
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_quote` - expected fees, e.g. from [`quote_send`]
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn get_transfer_fee_for_chain(
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_debridge_send_checked_signed(
+    send_ix: SendIx,
+    fee_quote: &FeeQuote,
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<Option<u64>, Error> {
-    get_chain_support_info(account_infos, target_chain_id).and_then(|chain_support_info| {
-        match chain_support_info {
-            ChainSupportInfo::NotSupported => Err(Error::TargetChainNotSupported),
-            ChainSupportInfo::Supported {
-                transfer_fee_bps, ..
-            } => Ok(transfer_fee_bps),
-        }
-    })
+    signers_seeds: &[&[&[u8]]],
+) -> Result<SendReceipt, ProgramError> {
+    SendAccounts::unchecked(account_infos).invoke_debridge_send_checked_signed(
+        send_ix,
+        fee_quote,
+        signers_seeds,
+    )
+}
+
+/// Check that balance decreased from `before` to `after` by exactly `expected`
+fn check_debited(before: u64, after: u64, expected: u64, error: Error) -> Result<(), Error> {
+    let debited = before.checked_sub(after);
+    if debited != Some(expected) {
+        msg!("Expected debited: {}, Actual: {:?}", expected, debited);
+        return Err(error);
+    }
+
+    Ok(())
 }
 
-/// Get native fixed fee for sending to target chain id
+/// Build send instruction of debridge program to add it directly into transaction
+/// instead of calling with CPI. Serialization is the same as in [`invoke_debridge_send`]
 ///
 /// # Arguments
-/// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn get_chain_native_fix_fee(
-    remaining_accounts: &[AccountInfo],
-    _target_chain_id: [u8; 32],
-) -> Result<u64, Error> {
-    match get_chain_support_info(remaining_accounts, _target_chain_id)? {
-        ChainSupportInfo::NotSupported => get_default_native_fix_fee(remaining_accounts),
-        ChainSupportInfo::Supported { fixed_fee, .. } => fixed_fee
-            .map(Ok)
-            .unwrap_or_else(|| get_default_native_fix_fee(remaining_accounts)),
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `accounts` - send accounts, e.g. from debridge-typescript-sdk or `client` feature resolver
+pub fn build_send_instruction(
+    send_ix: SendIx,
+    accounts: &[AccountMeta],
+) -> Result<Instruction, ProgramError> {
+    send_instruction(&send_ix, accounts.iter().map(|meta| &meta.pubkey))
+}
+
+fn send_instruction<'k>(
+    send_ix: &SendIx,
+    keys: impl Iterator<Item = &'k Pubkey>,
+) -> Result<Instruction, ProgramError> {
+    let mut accounts = Vec::with_capacity(SEND_META_TEMPLATE.len());
+    accounts.extend(
+        keys.take(SEND_META_TEMPLATE.len())
+            .zip(SEND_META_TEMPLATE)
+            .map(|(key, meta)| AccountMeta {
+                pubkey: *key,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            }),
+    );
+
+    if accounts.len() < SEND_META_TEMPLATE.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    if accounts[DEBRIDGE_PROGRAM_INDEX].pubkey.ne(&DEBRIDGE_ID) {
+        return Err(Error::WrongDebridgeProgramId.into());
     }
+
+    Ok(Instruction {
+        program_id: DEBRIDGE_ID,
+        accounts,
+        data: send_instruction_data(send_ix)?,
+    })
 }
 
-/// Get default native fixed fee
+/// Serialize send instruction data into a single buffer of exact size.
+/// Layout is borsh of [`SendIx`] with [`DEFAULT_REFERRAL_CODE`] applied
+fn send_instruction_data(send_ix: &SendIx) -> Result<Vec<u8>, ProgramError> {
+    let referral_code = send_ix.referral_code.or(DEFAULT_REFERRAL_CODE);
+
+    let mut data = Vec::with_capacity(send_instruction_data_len(send_ix, referral_code));
+    data.extend_from_slice(&SEND_DISCRIMINATOR);
+    send_ix.target_chain_id.serialize(&mut data)?;
+    send_ix.receiver.serialize(&mut data)?;
+    send_ix.is_use_asset_fee.serialize(&mut data)?;
+    send_ix.amount.serialize(&mut data)?;
+    send_ix.submission_params.serialize(&mut data)?;
+    referral_code.serialize(&mut data)?;
+
+    Ok(data)
+}
+
+fn send_instruction_data_len(send_ix: &SendIx, referral_code: Option<u32>) -> usize {
+    let submission_params_len = send_ix
+        .submission_params
+        .as_ref()
+        .map(|params| 8 + 32 + 4 + params.fallback_address.len() + 32)
+        .unwrap_or(0);
+
+    SEND_DISCRIMINATOR.len()
+        + 32
+        + 4
+        + send_ix.receiver.len()
+        + 1
+        + 8
+        + 1
+        + submission_params_len
+        + 1
+        + referral_code.map(|_| 4).unwrap_or(0)
+}
+
+/// Struct for forming send instruction in debridge program
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct InitExternalCallIx {
+    /// Len of external call array
+    pub external_call_len: u32,
+    /// Target chain id
+    pub chain_id: [u8; 32],
+    /// Keccak hash of external call
+    pub external_call_shortcut: [u8; 32],
+    /// Message that send and try to execute in target chain
+    pub external_call: Vec<u8>,
+}
+
+/// Create account for storing external call buffer
+///
+/// In the case of a large external call use [`invoke_upload_external_call_chunk`]
+/// to upload it by several transactions
+///
+/// If the storage already exists its content is verified with [`get_external_call_upload_state`]:
+/// partially uploaded external call is completed, stale or foreign content is rejected
 ///
 /// # Arguments
+/// * `external_call` - instructions sending in target chain
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-pub fn get_default_native_fix_fee(account_infos: &[AccountInfo]) -> Result<u64, Error> {
-    Ok(get_state(account_infos)?.global_fixed_fee)
+pub fn invoke_init_external_call(
+    external_call: &[u8],
+    account_infos: &[AccountInfo],
+) -> Result<(), ProgramError> {
+    invoke_init_external_call_signed(external_call, account_infos, &[])
 }
 
-/// Checks the availability of payment fixed fee in transferring tokens
+/// Create account for storing external call buffer with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
 ///
 /// # Arguments
+/// * `external_call` - instructions sending in target chain
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn is_asset_fee_available(
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_init_external_call_signed(
+    external_call: &[u8],
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<bool, Error> {
-    match get_asset_fee_info(account_infos, target_chain_id) {
-        Ok(asset_fee) => Ok(asset_fee.asset_chain_fee.is_some()),
-        Err(err) if err == Error::WrongAccountIndex => Err(err),
-        Err(_) => Ok(false),
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    SendAccounts::unchecked(account_infos)
+        .invoke_init_external_call_signed(external_call, signers_seeds)
+}
+
+/// Build init external call instruction of debridge program to add it directly into transaction
+/// instead of calling with CPI. Serialization is the same as in [`invoke_init_external_call`]
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `accounts` - send accounts, e.g. from debridge-typescript-sdk or `client` feature resolver
+pub fn build_init_external_call_instruction(
+    external_call: &[u8],
+    accounts: &[AccountMeta],
+) -> Result<Instruction, ProgramError> {
+    if accounts.len() < SEND_META_TEMPLATE.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
     }
+
+    init_external_call_instruction(
+        external_call.len(),
+        SolanaKeccak256::hash(external_call),
+        external_call,
+        &accounts[EXTERNAL_CALL_STORAGE_INDEX].pubkey,
+        &accounts[EXTERNAL_CALL_META_INDEX].pubkey,
+        &accounts[SEND_FROM_INDEX].pubkey,
+        &accounts[SYSTEM_PROGRAM_INDEX].pubkey,
+    )
+}
+
+fn init_external_call_instruction(
+    external_call_len: usize,
+    external_call_shortcut: [u8; 32],
+    chunk: &[u8],
+    external_call_storage: &Pubkey,
+    external_call_meta: &Pubkey,
+    send_from: &Pubkey,
+    system_program: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let mut data =
+        Vec::with_capacity(INIT_EXTERNAL_CALL_DISCRIMINATOR.len() + 4 + 32 + 32 + 4 + chunk.len());
+    data.extend_from_slice(&INIT_EXTERNAL_CALL_DISCRIMINATOR);
+    (external_call_len as u32).serialize(&mut data)?;
+    SOLANA_CHAIN_ID.serialize(&mut data)?;
+    external_call_shortcut.serialize(&mut data)?;
+    chunk.serialize(&mut data)?;
+
+    Ok(Instruction {
+        program_id: DEBRIDGE_ID,
+        data,
+        accounts: vec![
+            AccountMeta {
+                pubkey: *external_call_storage,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *external_call_meta,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *send_from,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *system_program,
+                is_signer: false,
+                is_writable: false,
+            },
+        ],
+    })
 }
 
-/// Try to get assets fixed fee for sending a current tokens to target chain id
+/// Struct for forming update external call instruction in debridge program
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UpdateExternalCallIx {
+    /// Target chain id
+    pub chain_id: [u8; 32],
+    /// Keccak hash of whole external call
+    pub external_call_shortcut: [u8; 32],
+    /// Offset of chunk in external call
+    pub offset: u64,
+    /// Chunk of external call
+    pub external_call: Vec<u8>,
+}
+
+/// Write chunk of external call into external call storage created by
+/// [`invoke_init_external_call`] in previous transactions
 ///
 /// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of whole external call
+/// * `offset` - offset of chunk in external call
+/// * `chunk` - part of external call starting from `offset`
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-pub fn try_get_chain_asset_fix_fee(
+pub fn invoke_update_external_call(
+    external_call_shortcut: [u8; 32],
+    offset: u64,
+    chunk: &[u8],
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-) -> Result<u64, Error> {
-    get_asset_fee_info(account_infos, target_chain_id)?
-        .asset_chain_fee
-        .ok_or(Error::AssetFeeNotSupported)
+) -> Result<(), ProgramError> {
+    invoke_update_external_call_signed(external_call_shortcut, offset, chunk, account_infos, &[])
 }
 
-const OVERFLOW_ERR: Error = Error::AmountOverflowedWhileAddingFee;
-
-/// Add all fees that will be taken to receive exact amount
+/// Write chunk of external call into external call storage with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
 ///
 /// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of whole external call
+/// * `offset` - offset of chunk in external call
+/// * `chunk` - part of external call starting from `offset`
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-/// * `exact_amount` - amount that will be received in target chain
-/// * `execution_fee` - amount of execution fee
-/// * `is_use_asset_fee` - determines how the fee will be paid. True: sending tokens, false: Sol
-pub fn add_all_fees(
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_update_external_call_signed(
+    external_call_shortcut: [u8; 32],
+    offset: u64,
+    chunk: &[u8],
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-    exact_amount: u64,
-    execution_fee: u64,
-    is_use_asset_fee: bool,
-) -> Result<u64, Error> {
-    add_transfer_fee(
-        account_infos,
-        target_chain_id,
-        exact_amount
-            .checked_add(execution_fee)
-            .ok_or(OVERFLOW_ERR)?
-            .checked_add(
-                is_use_asset_fee
-                    .then(|| try_get_chain_asset_fix_fee(account_infos, target_chain_id))
-                    .transpose()?
-                    .unwrap_or(0),
-            )
-            .ok_or(OVERFLOW_ERR)?,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    SendAccounts::unchecked(account_infos).invoke_update_external_call_signed(
+        external_call_shortcut,
+        offset,
+        chunk,
+        signers_seeds,
     )
 }
 
-/// Add transfer fee that will be taken to send exact amount to target chain
+/// Build update external call instruction of debridge program to add it directly into transaction
+/// instead of calling with CPI. Serialization is the same as in [`invoke_update_external_call`]
+///
+/// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of whole external call
+/// * `offset` - offset of chunk in external call
+/// * `chunk` - part of external call starting from `offset`
+/// * `accounts` - send accounts, e.g. from debridge-typescript-sdk or `client` feature resolver
+pub fn build_update_external_call_instruction(
+    external_call_shortcut: [u8; 32],
+    offset: u64,
+    chunk: &[u8],
+    accounts: &[AccountMeta],
+) -> Result<Instruction, ProgramError> {
+    if accounts.len() < SEND_META_TEMPLATE.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    update_external_call_instruction(
+        external_call_shortcut,
+        offset,
+        chunk,
+        &accounts[EXTERNAL_CALL_STORAGE_INDEX].pubkey,
+        &accounts[EXTERNAL_CALL_META_INDEX].pubkey,
+        &accounts[SEND_FROM_INDEX].pubkey,
+    )
+}
+
+fn update_external_call_instruction(
+    external_call_shortcut: [u8; 32],
+    offset: u64,
+    chunk: &[u8],
+    external_call_storage: &Pubkey,
+    external_call_meta: &Pubkey,
+    send_from: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let mut data = Vec::with_capacity(
+        UPDATE_EXTERNAL_CALL_DISCRIMINATOR.len() + 32 + 32 + 8 + 4 + chunk.len(),
+    );
+    data.extend_from_slice(&UPDATE_EXTERNAL_CALL_DISCRIMINATOR);
+    SOLANA_CHAIN_ID.serialize(&mut data)?;
+    external_call_shortcut.serialize(&mut data)?;
+    offset.serialize(&mut data)?;
+    chunk.serialize(&mut data)?;
+
+    Ok(Instruction {
+        program_id: DEBRIDGE_ID,
+        data,
+        accounts: vec![
+            AccountMeta {
+                pubkey: *external_call_storage,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *external_call_meta,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *send_from,
+                is_signer: true,
+                is_writable: true,
+            },
+        ],
+    })
+}
+
+/// Progress of external call upload by [`invoke_upload_external_call_chunk`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalCallUpload {
+    /// Only part of external call is uploaded. Call upload again in next transaction
+    InProgress {
+        /// Length of uploaded part of external call
+        uploaded_len: u64,
+    },
+    /// Whole external call is uploaded
+    Completed,
+}
+
+/// Size of external call storage header preceding external call bytes.
+///
+/// External call storage is expected to hold 8 bytes of Anchor account discriminator
+/// followed by raw external call bytes, with no length prefix. The layout isn't
+/// checked against a dump of a deBridge external call storage account yet, so
+/// `test_external_call_storage_layout` only covers how the SDK reads this assumed layout
+const EXTERNAL_CALL_STORAGE_HEADER_LEN: usize = 8;
+
+/// Get upload state of external call from external call meta and compare content of
+/// external call storage with `external_call`. Returns `InProgress { uploaded_len: 0 }`
+/// if external call meta is not created yet
 ///
 /// # Arguments
+/// * `external_call` - whole instructions sending in target chain
 /// * `account_infos` - account forming by client from debridge-typescript-sdk
-/// * `target_chain_id` - chain id to which the tokens are sent
-/// * `exact_amount` - amount that will be send in target chain
-pub fn add_transfer_fee(
+pub fn get_external_call_upload_state(
+    external_call: &[u8],
     account_infos: &[AccountInfo],
-    target_chain_id: [u8; 32],
-    exact_amount: u64,
-) -> Result<u64, Error> {
-    let transfer_fee_bps = get_transfer_fee(account_infos, target_chain_id)?;
-
-    u128::from(exact_amount)
-        .checked_mul(u128::from(BPS_DENOMINATOR))
-        .ok_or(OVERFLOW_ERR)?
-        .checked_div(u128::from(
-            BPS_DENOMINATOR
-                .checked_sub(transfer_fee_bps)
-                .ok_or(OVERFLOW_ERR)?,
-        ))
-        .ok_or(OVERFLOW_ERR)?
-        .try_into()
-        .map_err(|_| OVERFLOW_ERR)
+) -> Result<ExternalCallUpload, Error> {
+    SendAccounts::unchecked(account_infos).get_external_call_upload_state(external_call)
 }
 
-pub fn set_send_from_account<'a>(
-    account_infos: &mut [AccountInfo<'a>],
-    send_from: AccountInfo<'a>,
-    send_from_wallet: AccountInfo<'a>,
-) {
-    account_infos[SEND_FROM_INDEX] = send_from;
-    account_infos[SEND_FROM_WALLET_INDEX] = send_from_wallet;
+/// Upload next chunk of large external call into external call storage.
+/// Call it in consecutive transactions with the same `external_call` until
+/// [`ExternalCallUpload::Completed`] is returned. Upload is resumed from
+/// the length stored in [`ExternalCallMeta::Accumulation`] after the already
+/// uploaded part is verified with [`get_external_call_upload_state`]
+///
+/// # Arguments
+/// * `external_call` - whole instructions sending in target chain
+/// * `max_chunk_len` - max length of external call part uploaded in this transaction
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn invoke_upload_external_call_chunk(
+    external_call: &[u8],
+    max_chunk_len: usize,
+    account_infos: &[AccountInfo],
+) -> Result<ExternalCallUpload, ProgramError> {
+    invoke_upload_external_call_chunk_signed(external_call, max_chunk_len, account_infos, &[])
 }
 
-pub const CHAIN_SUPPORT_INFO_INDEX: usize = 4;
-pub const STATE_INDEX: usize = 7;
-pub const ASSET_FEE_INDEX: usize = 16;
-pub const TOKEN_MINT_INDEX: usize = 1;
+/// Upload next chunk of large external call with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
+///
+/// # Arguments
+/// * `external_call` - whole instructions sending in target chain
+/// * `max_chunk_len` - max length of external call part uploaded in this transaction
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_upload_external_call_chunk_signed(
+    external_call: &[u8],
+    max_chunk_len: usize,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<ExternalCallUpload, ProgramError> {
+    SendAccounts::unchecked(account_infos).invoke_upload_external_call_chunk_signed(
+        external_call,
+        max_chunk_len,
+        signers_seeds,
+    )
+}
 
-pub const EXTERNAL_CALL_STORAGE_INDEX: usize = 12;
-pub const EXTERNAL_CALL_META_INDEX: usize = 13;
-pub const SEND_FROM_INDEX: usize = 14;
-pub const SEND_FROM_WALLET_INDEX: usize = 10;
-pub const SYSTEM_PROGRAM_INDEX: usize = 11;
-pub const DEBRIDGE_PROGRAM_INDEX: usize = 17;
+/// Struct for forming close external call instruction in debridge program
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CloseExternalCallIx {
+    /// Target chain id
+    pub chain_id: [u8; 32],
+    /// Keccak hash of external call
+    pub external_call_shortcut: [u8; 32],
+}
 
-struct MetaTemplate {
-    is_signer: bool,
-    is_writable: bool,
+/// Close external call storage and meta accounts and return their rent to send_from.
+/// It's possible only when external call meta is
+/// [`ExternalCallMeta::Transferred`] or [`ExternalCallMeta::Executed`]
+///
+/// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of external call
+/// * `external_call_storage` - external call storage of send_from
+/// * `external_call_meta` - meta of external call storage
+/// * `send_from` - owner of external call storage, rent is returned to it
+/// * `debridge_program` - debridge program account
+pub fn invoke_close_external_call<'info>(
+    external_call_shortcut: [u8; 32],
+    external_call_storage: &AccountInfo<'info>,
+    external_call_meta: &AccountInfo<'info>,
+    send_from: &AccountInfo<'info>,
+    debridge_program: &AccountInfo<'info>,
+) -> Result<(), ProgramError> {
+    invoke_close_external_call_signed(
+        external_call_shortcut,
+        external_call_storage,
+        external_call_meta,
+        send_from,
+        debridge_program,
+        &[],
+    )
 }
 
-const SEND_META_TEMPLATE: [MetaTemplate; 18] = [
-    // 0: Bridge
-    MetaTemplate {
-        is_signer: false,
-        is_writable: true,
-    },
-    // 1: Token Mint
+/// Close external call storage and meta accounts with using PDA account as send_from.
+/// You can using this function for reclaiming rent on behalf of the program.
+///
+/// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of external call
+/// * `external_call_storage` - external call storage of send_from
+/// * `external_call_meta` - meta of external call storage
+/// * `send_from` - owner of external call storage, rent is returned to it
+/// * `debridge_program` - debridge program account
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_close_external_call_signed<'info>(
+    external_call_shortcut: [u8; 32],
+    external_call_storage: &AccountInfo<'info>,
+    external_call_meta: &AccountInfo<'info>,
+    send_from: &AccountInfo<'info>,
+    debridge_program: &AccountInfo<'info>,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    if debridge_program.key.ne(&DEBRIDGE_ID) {
+        return Err(Error::WrongDebridgeProgramId.into());
+    }
+
+    if external_call_meta.owner.ne(&DEBRIDGE_ID)
+        || !ExternalCallMeta::try_from_account(external_call_meta)?.is_reclaimable()
+    {
+        return Err(Error::ExternalStorageWrongState.into());
+    }
+
+    invoke_signed(
+        &close_external_call_instruction(
+            external_call_shortcut,
+            external_call_storage.key,
+            external_call_meta.key,
+            send_from.key,
+        )?,
+        &[
+            external_call_storage.clone(),
+            external_call_meta.clone(),
+            send_from.clone(),
+            debridge_program.clone(),
+        ],
+        signers_seeds,
+    )
+}
+
+/// Build close external call instruction of debridge program to add it directly into transaction
+/// instead of calling with CPI. Serialization is the same as in [`invoke_close_external_call`]
+///
+/// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of external call
+/// * `accounts` - send accounts, e.g. from debridge-typescript-sdk or `client` feature resolver
+pub fn build_close_external_call_instruction(
+    external_call_shortcut: [u8; 32],
+    accounts: &[AccountMeta],
+) -> Result<Instruction, ProgramError> {
+    if accounts.len() < SEND_META_TEMPLATE.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    close_external_call_instruction(
+        external_call_shortcut,
+        &accounts[EXTERNAL_CALL_STORAGE_INDEX].pubkey,
+        &accounts[EXTERNAL_CALL_META_INDEX].pubkey,
+        &accounts[SEND_FROM_INDEX].pubkey,
+    )
+}
+
+pub(crate) fn close_external_call_instruction(
+    external_call_shortcut: [u8; 32],
+    external_call_storage: &Pubkey,
+    external_call_meta: &Pubkey,
+    send_from: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    Ok(Instruction::new_with_bytes(
+        DEBRIDGE_ID,
+        &[
+            CLOSE_EXTERNAL_CALL_DISCRIMINATOR.as_slice(),
+            CloseExternalCallIx {
+                chain_id: SOLANA_CHAIN_ID,
+                external_call_shortcut,
+            }
+            .try_to_vec()
+            .map_err(ProgramError::from)?
+            .as_slice(),
+        ]
+        .concat(),
+        vec![
+            AccountMeta {
+                pubkey: *external_call_storage,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *external_call_meta,
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: *send_from,
+                is_signer: true,
+                is_writable: true,
+            },
+        ],
+    ))
+}
+
+/// Send message to other chain without liquidity.
+/// Perform debridge send flow with zero amount
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - send message to other chain without liquidity.
+/// * `execution_fee` - chain id to which the tokens are sent
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn invoke_send_message(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    referral_code: Option<u32>,
+    account_infos: &[AccountInfo],
+) -> Result<(), InvokeError> {
+    invoke_send_message_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        execution_fee,
+        fallback_address,
+        referral_code,
+        account_infos,
+        &[],
+    )
+}
+
+/// Send message to other chain without liquidity with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
+/// Perform debridge send flow with zero amount.
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - send message to other chain without liquidity.
+/// * `execution_fee` - chain id to which the tokens are sent
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_message_signed(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    referral_code: Option<u32>,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), InvokeError> {
+    SendAccounts::unchecked(account_infos).invoke_send_message_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        execution_fee,
+        fallback_address,
+        referral_code,
+        signers_seeds,
+    )
+}
+
+/// Send message to other chain without liquidity if live fees don't exceed `fee_limits`.
+/// Fees are checked with [`check_fee_limits`] before sending
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - send message to other chain without liquidity.
+/// * `execution_fee` - chain id to which the tokens are sent
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `fee_limits` - fee limits accepted by sender
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_message_with_fee_limits(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    referral_code: Option<u32>,
+    fee_limits: &FeeLimits,
+    account_infos: &[AccountInfo],
+) -> Result<(), InvokeError> {
+    invoke_send_message_with_fee_limits_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        execution_fee,
+        fallback_address,
+        referral_code,
+        fee_limits,
+        account_infos,
+        &[],
+    )
+}
+
+/// Send message to other chain without liquidity if live fees don't exceed `fee_limits`
+/// with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - send message to other chain without liquidity.
+/// * `execution_fee` - chain id to which the tokens are sent
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `fee_limits` - fee limits accepted by sender
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_message_with_fee_limits_signed(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    referral_code: Option<u32>,
+    fee_limits: &FeeLimits,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), InvokeError> {
+    SendAccounts::unchecked(account_infos).invoke_send_message_with_fee_limits_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        execution_fee,
+        fallback_address,
+        referral_code,
+        fee_limits,
+        signers_seeds,
+    )
+}
+
+/// Send tokens with external call to other chain.
+/// Perform the whole flow: create external call storage, add all fees to the exact amount
+/// and send with [`SendSubmissionParamsInput::with_external_call`] submission params
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - address in target chain receiving tokens or executing external call
+/// * `exact_amount` - amount that will be received in target chain
+/// * `execution_fee` - amount of execution fee
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `flags` - flags for additional debridge protocol features
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_with_external_call(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    exact_amount: u64,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    flags: [u8; 32],
+    referral_code: Option<u32>,
+    account_infos: &[AccountInfo],
+) -> Result<SendReceipt, InvokeError> {
+    invoke_send_with_external_call_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        exact_amount,
+        execution_fee,
+        fallback_address,
+        flags,
+        referral_code,
+        account_infos,
+        &[],
+    )
+}
+
+/// Send tokens with external call to other chain with using PDA account as send_from.
+/// You can using this function for sending tokens on behalf of the program.
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - address in target chain receiving tokens or executing external call
+/// * `exact_amount` - amount that will be received in target chain
+/// * `execution_fee` - amount of execution fee
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `flags` - flags for additional debridge protocol features
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_with_external_call_signed(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    exact_amount: u64,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    flags: [u8; 32],
+    referral_code: Option<u32>,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<SendReceipt, InvokeError> {
+    SendAccounts::unchecked(account_infos).invoke_send_with_external_call_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        exact_amount,
+        execution_fee,
+        fallback_address,
+        flags,
+        referral_code,
+        signers_seeds,
+    )
+}
+
+/// Send tokens with external call to other chain if live fees don't exceed `fee_limits`.
+/// Fees are checked with [`check_fee_limits`] before sending
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - address in target chain receiving tokens or executing external call
+/// * `exact_amount` - amount that will be received in target chain
+/// * `execution_fee` - amount of execution fee
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `flags` - flags for additional debridge protocol features
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `fee_limits` - fee limits accepted by sender
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_with_external_call_with_fee_limits(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    exact_amount: u64,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    flags: [u8; 32],
+    referral_code: Option<u32>,
+    fee_limits: &FeeLimits,
+    account_infos: &[AccountInfo],
+) -> Result<SendReceipt, InvokeError> {
+    invoke_send_with_external_call_with_fee_limits_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        exact_amount,
+        execution_fee,
+        fallback_address,
+        flags,
+        referral_code,
+        fee_limits,
+        account_infos,
+        &[],
+    )
+}
+
+/// Send tokens with external call to other chain if live fees don't exceed `fee_limits`
+/// with using PDA account as send_from.
+/// You can using this function for sending tokens on behalf of the program.
+///
+/// # Arguments
+/// * `external_call` - instructions sending in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - address in target chain receiving tokens or executing external call
+/// * `exact_amount` - amount that will be received in target chain
+/// * `execution_fee` - amount of execution fee
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `flags` - flags for additional debridge protocol features
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `fee_limits` - fee limits accepted by sender
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_with_external_call_with_fee_limits_signed(
+    external_call: Vec<u8>,
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    exact_amount: u64,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    flags: [u8; 32],
+    referral_code: Option<u32>,
+    fee_limits: &FeeLimits,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<SendReceipt, InvokeError> {
+    SendAccounts::unchecked(account_infos).invoke_send_with_external_call_with_fee_limits_signed(
+        external_call,
+        target_chain_id,
+        receiver,
+        exact_amount,
+        execution_fee,
+        fallback_address,
+        flags,
+        referral_code,
+        fee_limits,
+        signers_seeds,
+    )
+}
+
+/// Send message to other chain without liquidity and without storing external call in Solana.
+/// Only keccak256 hash of external call is sent, executor provides external call in target chain.
+/// External call storage isn't created, so no rent is paid for it
+///
+/// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of instructions executed in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - send message to other chain without liquidity.
+/// * `execution_fee` - chain id to which the tokens are sent
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn invoke_send_hashed_message(
+    external_call_shortcut: [u8; 32],
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    referral_code: Option<u32>,
+    account_infos: &[AccountInfo],
+) -> Result<SendReceipt, InvokeError> {
+    invoke_send_hashed_message_signed(
+        external_call_shortcut,
+        target_chain_id,
+        receiver,
+        execution_fee,
+        fallback_address,
+        referral_code,
+        account_infos,
+        &[],
+    )
+}
+
+/// Send message to other chain without liquidity and without storing external call in Solana
+/// with using PDA account as send_from.
+/// You can using this function for sending message on behalf of the program.
+///
+/// # Arguments
+/// * `external_call_shortcut` - keccak256 hash of instructions executed in target chain
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `receiver` - send message to other chain without liquidity.
+/// * `execution_fee` - chain id to which the tokens are sent
+/// * `fallback_address` - reserve address for sending tokens if external call fails
+/// * `referral_code` - referral code of integrator. [`DEFAULT_REFERRAL_CODE`] is used if `None`
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_send_hashed_message_signed(
+    external_call_shortcut: [u8; 32],
+    target_chain_id: [u8; 32],
+    receiver: Vec<u8>,
+    execution_fee: u64,
+    fallback_address: Vec<u8>,
+    referral_code: Option<u32>,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<SendReceipt, InvokeError> {
+    SendAccounts::unchecked(account_infos).invoke_send_hashed_message_signed(
+        external_call_shortcut,
+        target_chain_id,
+        receiver,
+        execution_fee,
+        fallback_address,
+        referral_code,
+        signers_seeds,
+    )
+}
+
+/// Get State account structure from sending accounts
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn get_state(account_infos: &[AccountInfo]) -> Result<State, Error> {
+    get_account_by_index(account_infos, STATE_INDEX)
+}
+
+/// Get nonce that will be used by the next send from nonce storage of sending accounts
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn get_nonce(account_infos: &[AccountInfo]) -> Result<u64, Error> {
+    get_account_by_index::<NonceStorage>(account_infos, NONCE_STORAGE_INDEX)
+        .map(|nonce_storage| nonce_storage.nonce)
+}
+
+/// Get Bridge account structure of sending token from sending accounts
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn get_bridge(account_infos: &[AccountInfo]) -> Result<Bridge, Error> {
+    get_account_by_index(account_infos, BRIDGE_INDEX)
+}
+
+/// Get Chain Support info account  account structure from sending accounts
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn get_chain_support_info(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<ChainSupportInfo, Error> {
+    SendAccounts::unchecked(account_infos).get_chain_support_info(target_chain_id)
+}
+
+/// Check that provided chain support info account refers to `target_chain_id`
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent}
+pub fn check_chain_support_info_account(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<(), Error> {
+    SendAccounts::unchecked(account_infos).check_chain_support_info_account(target_chain_id)
+}
+
+/// Get Bridge asset fee info account account structure from sending accounts
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn get_asset_fee_info(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<AssetFeeInfo, Error> {
+    SendAccounts::unchecked(account_infos).get_asset_fee_info(target_chain_id)
+}
+
+/// Parse account structure from sending accounts by index
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `account_index` - account index from sending accounts  
+pub fn get_account_by_index<T: TryFromAccount<Error = Error>>(
+    account_infos: &[AccountInfo],
+    account_index: usize,
+) -> Result<T, Error> {
+    if account_infos.len() <= account_index {
+        return Err(Error::WrongAccountIndex);
+    }
+    T::try_from_account(&account_infos[account_index])
+}
+
+/// Check the possibility of sending to the chain by chain id
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn is_chain_supported(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<bool, Error> {
+    SendAccounts::unchecked(account_infos).is_chain_supported(target_chain_id)
+}
+
+/// Get length of address in target chain
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn get_chain_address_len(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<u16, Error> {
+    SendAccounts::unchecked(account_infos).get_chain_address_len(target_chain_id)
+}
+
+/// Get transfer fee bps for sending current tokens to target chain id.
+/// Discount of sender is applied, as debridge program takes the fee
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn get_transfer_fee(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).get_transfer_fee(target_chain_id)
+}
+
+/// Some networks have their own transfer fee bps
+/// Get own transfer fee bps to target chain id if defined
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn get_transfer_fee_for_chain(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<Option<u64>, Error> {
+    SendAccounts::unchecked(account_infos).get_transfer_fee_for_chain(target_chain_id)
+}
+
+/// Get native fixed fee for sending to target chain id.
+/// Discount of sender is applied, as debridge program takes the fee
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn get_chain_native_fix_fee(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).get_chain_native_fix_fee(target_chain_id)
+}
+
+/// Get default native fixed fee
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn get_default_native_fix_fee(account_infos: &[AccountInfo]) -> Result<u64, Error> {
+    Ok(get_state(account_infos)?.global_fixed_fee)
+}
+
+/// Checks the availability of payment fixed fee in transferring tokens
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn is_asset_fee_available(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<bool, Error> {
+    SendAccounts::unchecked(account_infos).is_asset_fee_available(target_chain_id)
+}
+
+/// Try to get assets fixed fee for sending a current tokens to target chain id.
+/// Discount of sender is applied, as debridge program takes the fee
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+pub fn try_get_chain_asset_fix_fee(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).try_get_chain_asset_fix_fee(target_chain_id)
+}
+
+/// Get fee discount of sender. Returns zero discount if
+/// no discount account is provided
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn get_discount(account_infos: &[AccountInfo]) -> Result<DiscountInfo, Error> {
+    SendAccounts::unchecked(account_infos).get_discount()
+}
+
+fn apply_discount(fee: u64, discount_bps: u64) -> Result<u64, Error> {
+    if discount_bps > BPS_DENOMINATOR {
+        return Err(Error::DiscountBpsExceedsDenominator);
+    }
+
+    let discount: u64 = u128::from(fee)
+        .checked_mul(u128::from(discount_bps))
+        .ok_or(OVERFLOW_ERR)?
+        .checked_div(u128::from(BPS_DENOMINATOR))
+        .ok_or(OVERFLOW_ERR)?
+        .try_into()
+        .map_err(|_| OVERFLOW_ERR)?;
+
+    fee.checked_sub(discount).ok_or(OVERFLOW_ERR)
+}
+
+/// Add all fees that will be taken to receive exact amount.
+/// Discount of sender is applied to fixed and transfer fees.
+/// Token-2022 transfer fee of sending token mint is added too
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `exact_amount` - amount that will be received in target chain
+/// * `execution_fee` - amount of execution fee
+/// * `is_use_asset_fee` - determines how the fee will be paid. True: sending tokens, false: Sol
+pub fn add_all_fees(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+    exact_amount: u64,
+    execution_fee: u64,
+    is_use_asset_fee: bool,
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).add_all_fees(
+        target_chain_id,
+        exact_amount,
+        execution_fee,
+        is_use_asset_fee,
+    )
+}
+
+fn get_token_mint<'a, 'info>(
+    account_infos: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>, Error> {
+    account_infos
+        .get(TOKEN_MINT_INDEX)
+        .ok_or(Error::WrongAccountIndex)
+}
+
+/// Add transfer fee that will be taken to send exact amount to target chain
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `exact_amount` - amount that will be send in target chain
+pub fn add_transfer_fee(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+    exact_amount: u64,
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).add_transfer_fee(target_chain_id, exact_amount)
+}
+
+/// Add transfer fee with `transfer_fee_bps` to exact amount
+///
+/// Division is rounded down, so the result is the largest amount that
+/// is received as `exact_amount`. Sending one token less may still deliver
+/// `exact_amount`, sending one token more always delivers more than `exact_amount`
+///
+/// # Arguments
+/// * `exact_amount` - amount that will be send in target chain
+/// * `transfer_fee_bps` - transfer fee in basis points
+pub fn add_transfer_fee_bps(exact_amount: u64, transfer_fee_bps: u64) -> Result<u64, Error> {
+    u128::from(exact_amount)
+        .checked_mul(u128::from(BPS_DENOMINATOR))
+        .ok_or(OVERFLOW_ERR)?
+        .checked_div(u128::from(
+            BPS_DENOMINATOR
+                .checked_sub(transfer_fee_bps)
+                .ok_or(OVERFLOW_ERR)?,
+        ))
+        .ok_or(OVERFLOW_ERR)?
+        .try_into()
+        .map_err(|_| OVERFLOW_ERR)
+}
+
+/// Subtract all fees that will be taken from amount. Inverse of [`add_all_fees`]
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `amount` - amount that will be debited from sender
+/// * `execution_fee` - amount of execution fee
+/// * `is_use_asset_fee` - determines how the fee will be paid. True: sending tokens, false: Sol
+pub fn subtract_all_fees(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+    amount: u64,
+    execution_fee: u64,
+    is_use_asset_fee: bool,
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).subtract_all_fees(
+        target_chain_id,
+        amount,
+        execution_fee,
+        is_use_asset_fee,
+    )
+}
+
+/// Get amount that will be received in target chain together with execution fee
+/// when `amount` is debited from sender. Use it to calculate the amount left for
+/// the execution fee. Token-2022 transfer fee of sending token mint is subtracted too
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `amount` - amount that will be debited from sender
+/// * `is_use_asset_fee` - determines how the fee will be paid. True: sending tokens, false: Sol
+pub fn received_amount_for(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+    amount: u64,
+    is_use_asset_fee: bool,
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).received_amount_for(
+        target_chain_id,
+        amount,
+        is_use_asset_fee,
+    )
+}
+
+/// Subtract transfer fee that will be taken from amount sent to target chain.
+/// Inverse of [`add_transfer_fee`]
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `amount` - amount that will be debited from sender
+pub fn subtract_transfer_fee(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+    amount: u64,
+) -> Result<u64, Error> {
+    SendAccounts::unchecked(account_infos).subtract_transfer_fee(target_chain_id, amount)
+}
+
+/// Subtract transfer fee with `transfer_fee_bps` from amount.
+/// Fee is rounded down as in debridge program
+///
+/// # Arguments
+/// * `amount` - amount that will be debited from sender
+/// * `transfer_fee_bps` - transfer fee in basis points
+pub fn subtract_transfer_fee_bps(amount: u64, transfer_fee_bps: u64) -> Result<u64, Error> {
+    let transfer_fee: u64 = u128::from(amount)
+        .checked_mul(u128::from(transfer_fee_bps))
+        .ok_or(OVERFLOW_ERR)?
+        .checked_div(u128::from(BPS_DENOMINATOR))
+        .ok_or(OVERFLOW_ERR)?
+        .try_into()
+        .map_err(|_| OVERFLOW_ERR)?;
+
+    amount
+        .checked_sub(transfer_fee)
+        .ok_or(Error::AmountTooSmallToCoverFees)
+}
+
+/// Amount for which send is quoted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteAmount {
+    /// Amount that will be debited from sender. Fees are taken from it
+    Amount(u64),
+    /// Amount that will be received in target chain. Fees are added to it
+    ExactAmount(u64),
+}
+
+/// Breakdown of send fees
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeeQuote {
+    /// Fixed fee in lamports. Zero if fee is paid in sending tokens
+    pub native_fix_fee: u64,
+    /// Fixed fee in sending tokens. Zero if fee is paid in lamports
+    pub asset_fix_fee: u64,
+    /// Transfer fee in sending tokens
+    pub transfer_fee: u64,
+    /// Token-2022 transfer fee withheld from sending tokens. Zero for mints without transfer fee
+    pub token_transfer_fee: u64,
+    /// Execution fee in sending tokens
+    pub execution_fee: u64,
+    /// Rent in lamports for external call storage and meta accounts
+    pub external_call_rent: u64,
+    /// Amount of tokens debited from sender. Use it as [`SendIx::amount`]
+    pub amount: u64,
+    /// Amount of tokens that will be received in target chain
+    pub received_amount: u64,
+}
+
+impl FeeQuote {
+    /// Total lamports spent by sender
+    pub fn total_lamports(&self) -> Option<u64> {
+        self.native_fix_fee.checked_add(self.external_call_rent)
+    }
+}
+
+/// Quote all fees of send to target chain.
+/// Discount of sender is applied to fixed and transfer fees, as debridge program takes them
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `quote_amount` - amount debited from sender or amount received in target chain
+/// * `execution_fee` - amount of execution fee
+/// * `is_use_asset_fee` - determines how the fee will be paid. True: sending tokens, false: Sol
+/// * `external_call_len` - length of external call. Zero if send is without external call
+pub fn quote_send(
+    account_infos: &[AccountInfo],
+    target_chain_id: [u8; 32],
+    quote_amount: QuoteAmount,
+    execution_fee: u64,
+    is_use_asset_fee: bool,
+    external_call_len: usize,
+) -> Result<FeeQuote, Error> {
+    SendAccounts::unchecked(account_infos).quote_send(
+        target_chain_id,
+        quote_amount,
+        execution_fee,
+        is_use_asset_fee,
+        external_call_len,
+    )
+}
+
+/// Check that every account of the send context is derived from provided parameters.
+/// Use it when sending accounts are provided by untrusted client.
+///
+/// Returns error with the first account that doesn't match.
+/// Token program and wallets are expected for the program owning token mint,
+/// so both spl-token and Token-2022 mints are supported
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `token_mint` - mint of sending tokens
+/// * `target_chain_id` - chain id to which the tokens are sent
+/// * `send_from` - sender of tokens, owner of the send-from wallet and external call storage
+/// * `external_call_shortcut` - keccak256 hash of external call buffer
+pub fn validate_send_accounts(
+    account_infos: &[AccountInfo],
+    token_mint: &Pubkey,
+    target_chain_id: [u8; 32],
+    send_from: &Pubkey,
+    external_call_shortcut: [u8; 32],
+) -> Result<(), Error> {
+    SendAccounts::unchecked(account_infos).validate(
+        token_mint,
+        target_chain_id,
+        send_from,
+        external_call_shortcut,
+    )
+}
+
+fn check_account_key(account: &AccountInfo, expected: &Pubkey, err: Error) -> Result<(), Error> {
+    if account.key.ne(expected) {
+        msg!("Expected: {}, Actual: {}", expected, account.key);
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+pub fn set_send_from_account<'a>(
+    account_infos: &mut [AccountInfo<'a>],
+    send_from: AccountInfo<'a>,
+    send_from_wallet: AccountInfo<'a>,
+) -> Result<(), Error> {
+    *account_infos
+        .get_mut(SEND_FROM_INDEX)
+        .ok_or(Error::WrongAccountIndex)? = send_from;
+    *account_infos
+        .get_mut(SEND_FROM_WALLET_INDEX)
+        .ok_or(Error::WrongAccountIndex)? = send_from_wallet;
+
+    Ok(())
+}
+
+/// Typed view over the accounts of the debridge send instruction
+///
+/// Checks once that all accounts of the send context are provided
+/// and gives named access to them, so callers don't need to work with indexes.
+/// Lookups use bumps set with [`SendAccounts::with_bumps`]
+#[derive(Clone, Copy)]
+pub struct SendAccounts<'a, 'info> {
+    account_infos: &'a [AccountInfo<'info>],
+    bumps: SendBumps,
+}
+
+impl<'a, 'info> TryFrom<&'a [AccountInfo<'info>]> for SendAccounts<'a, 'info> {
+    type Error = Error;
+
+    fn try_from(account_infos: &'a [AccountInfo<'info>]) -> Result<Self, Self::Error> {
+        if account_infos.len() < SEND_META_TEMPLATE.len() {
+            return Err(Error::WrongAccountIndex);
+        }
+
+        Ok(SendAccounts {
+            account_infos,
+            bumps: SendBumps::default(),
+        })
+    }
+}
+
+impl<'a, 'info> SendAccounts<'a, 'info> {
+    /// Create typed view from sending accounts
+    ///
+    /// # Arguments
+    /// * `account_infos` - account forming by client from debridge-typescript-sdk
+    pub fn new(account_infos: &'a [AccountInfo<'info>]) -> Result<Self, Error> {
+        Self::try_from(account_infos)
+    }
+
+    /// View over accounts without checking that all accounts of the send context
+    /// are provided. Lookups of missing accounts fail with [`Error::WrongAccountIndex`]
+    fn unchecked(account_infos: &'a [AccountInfo<'info>]) -> Self {
+        SendAccounts {
+            account_infos,
+            bumps: SendBumps::default(),
+        }
+    }
+
+    /// All provided accounts, including accounts after the send context
+    pub fn account_infos(&self) -> &'a [AccountInfo<'info>] {
+        self.account_infos
+    }
+
+    /// debridge-settings [`Bridge`] account of the sent token
+    pub fn bridge(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[BRIDGE_INDEX]
+    }
+
+    /// Mint of the sent token
+    pub fn token_mint(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[TOKEN_MINT_INDEX]
+    }
+
+    /// Token account of the bridge where fees in sent token are accumulated
+    pub fn staking_wallet(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[STAKING_WALLET_INDEX]
+    }
+
+    /// Authority of the bridge owning its token accounts
+    pub fn mint_authority(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[MINT_AUTHORITY_INDEX]
+    }
+
+    /// [`ChainSupportInfo`] account of the target chain
+    pub fn chain_support_info(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[CHAIN_SUPPORT_INFO_INDEX]
+    }
+
+    /// debridge-settings program
+    pub fn settings_program(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[SETTINGS_PROGRAM_INDEX]
+    }
+
+    /// Token program of the sent token, spl-token or spl-token-2022
+    pub fn token_program(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[TOKEN_PROGRAM_INDEX]
+    }
+
+    /// debridge-settings [`State`] account
+    pub fn state(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[STATE_INDEX]
+    }
+
+    /// Account receiving native fix fee
+    pub fn fee_beneficiary(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[FEE_BENEFICIARY_INDEX]
+    }
+
+    /// debridge nonce storage account
+    pub fn nonce_storage(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[NONCE_STORAGE_INDEX]
+    }
+
+    /// Token account of `send_from` debited by the send
+    pub fn send_from_wallet(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[SEND_FROM_WALLET_INDEX]
+    }
+
+    /// System program
+    pub fn system_program(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[SYSTEM_PROGRAM_INDEX]
+    }
+
+    /// Account storing external call of the submission
+    pub fn external_call_storage(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[EXTERNAL_CALL_STORAGE_INDEX]
+    }
+
+    /// Account storing state of the external call storage
+    pub fn external_call_meta(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[EXTERNAL_CALL_META_INDEX]
+    }
+
+    /// Signer of the send paying amount and fees
+    pub fn send_from(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[SEND_FROM_INDEX]
+    }
+
+    /// [`DiscountInfo`] account of `send_from`
+    pub fn discount(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[DISCOUNT_INDEX]
+    }
+
+    /// [`AssetFeeInfo`] account of the bridge for the target chain
+    pub fn asset_fee(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[ASSET_FEE_INDEX]
+    }
+
+    /// debridge program
+    pub fn debridge_program(&self) -> &'a AccountInfo<'info> {
+        &self.account_infos[DEBRIDGE_PROGRAM_INDEX]
+    }
+
+    /// Same as [`validate_send_accounts`]
+    pub fn validate(
+        &self,
+        token_mint: &Pubkey,
+        target_chain_id: [u8; 32],
+        send_from: &Pubkey,
+        external_call_shortcut: [u8; 32],
+    ) -> Result<(), Error> {
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+
+        let bridge = self.bumps.bridge_address(token_mint)?;
+        check_account_key(accounts.bridge(), &bridge, Error::WrongBridge)?;
+        check_account_key(accounts.token_mint(), token_mint, Error::WrongTokenMint)?;
+        let token_program = token::get_token_program_id(accounts.token_mint())?;
+        let mint_authority = self.bumps.mint_authority_address(&bridge)?;
+        check_account_key(
+            accounts.staking_wallet(),
+            &SendBumps::associated_token_address(
+                self.bumps.staking_wallet,
+                &mint_authority,
+                token_mint,
+                &token_program,
+                Error::WrongStakingWallet,
+            )?,
+            Error::WrongStakingWallet,
+        )?;
+        check_account_key(
+            accounts.mint_authority(),
+            &mint_authority,
+            Error::WrongMintAuthority,
+        )?;
+        self.check_chain_support_info_account(target_chain_id)?;
+        check_account_key(
+            accounts.settings_program(),
+            &SETTINGS_ID,
+            Error::WrongSettingProgramId,
+        )?;
+        check_account_key(
+            accounts.token_program(),
+            &token_program,
+            Error::WrongTokenProgramId,
+        )?;
+        check_account_key(accounts.state(), &STATE_ADDRESS, Error::WrongState)?;
+        check_account_key(
+            accounts.fee_beneficiary(),
+            &accounts.get_state()?.fee_beneficiary,
+            Error::WrongFeeBeneficiary,
+        )?;
+        check_account_key(
+            accounts.nonce_storage(),
+            &NONCE_STORAGE_ADDRESS,
+            Error::WrongNonceStorage,
+        )?;
+        check_account_key(
+            accounts.send_from_wallet(),
+            &SendBumps::associated_token_address(
+                self.bumps.send_from_wallet,
+                send_from,
+                token_mint,
+                &token_program,
+                Error::WrongSendFromWallet,
+            )?,
+            Error::WrongSendFromWallet,
+        )?;
+        check_account_key(
+            accounts.system_program(),
+            &system_program::ID,
+            Error::WrongSystemProgramId,
+        )?;
+
+        let external_call_storage = self
+            .bumps
+            .external_call_storage_address(&external_call_shortcut, send_from)?;
+        check_account_key(
+            accounts.external_call_storage(),
+            &external_call_storage,
+            Error::WrongExternalCallStorage,
+        )?;
+        check_account_key(
+            accounts.external_call_meta(),
+            &self
+                .bumps
+                .external_call_meta_address(&external_call_storage)?,
+            Error::WrongExternalCallMeta,
+        )?;
+        check_account_key(accounts.send_from(), send_from, Error::WrongSendFrom)?;
+
+        let discount = accounts.discount().key;
+        if NO_DISCOUNT_ADDRESS.ne(discount)
+            && self.bumps.discount_info_address(send_from)?.ne(discount)
+        {
+            msg!("Unexpected discount account: {}", discount);
+            return Err(Error::WrongDiscount);
+        }
+
+        let asset_fee = accounts.asset_fee().key;
+        if DEFAULT_BRIDGE_FEE_ADDRESS.ne(asset_fee)
+            && self
+                .bumps
+                .asset_fee_info_address(&bridge, &target_chain_id)?
+                .ne(asset_fee)
+        {
+            msg!("Unexpected asset fee account: {}", asset_fee);
+            return Err(Error::WrongBridgeFeeInfo);
+        }
+
+        check_account_key(
+            accounts.debridge_program(),
+            &DEBRIDGE_ID,
+            Error::WrongDebridgeProgramId,
+        )
+    }
+
+    /// Same as [`get_state`]
+    pub fn get_state(&self) -> Result<State, Error> {
+        get_state(self.account_infos)
+    }
+
+    /// Same as [`get_discount`]
+    pub fn get_discount(&self) -> Result<DiscountInfo, Error> {
+        let (send_from, discount) = self
+            .account_infos
+            .get(SEND_FROM_INDEX)
+            .zip(self.account_infos.get(DISCOUNT_INDEX))
+            .ok_or(Error::WrongAccountIndex)?;
+
+        if NO_DISCOUNT_ADDRESS.eq(discount.key) {
+            Ok(DiscountInfo::default())
+        } else if self
+            .bumps
+            .discount_info_address(send_from.key)?
+            .eq(discount.key)
+        {
+            get_account_by_index(self.account_infos, DISCOUNT_INDEX)
+        } else {
+            Err(Error::WrongDiscount)
+        }
+    }
+
+    /// Same as [`token::get_transfer_fee_config`] for sending token mint
+    pub fn get_token_transfer_fee_config(&self) -> Result<Option<TransferFeeConfig>, Error> {
+        token::get_transfer_fee_config(self.token_mint())
+    }
+
+    /// Same as [`get_external_call_upload_state`]
+    pub fn get_external_call_upload_state(
+        &self,
+        external_call: &[u8],
+    ) -> Result<ExternalCallUpload, Error> {
+        if self.account_infos.len() <= EXTERNAL_CALL_STORAGE_INDEX.max(EXTERNAL_CALL_META_INDEX) {
+            return Err(Error::WrongAccountIndex);
+        }
+
+        let external_call_meta = &self.account_infos[EXTERNAL_CALL_META_INDEX];
+        if external_call_meta.owner.ne(&DEBRIDGE_ID) {
+            return Ok(ExternalCallUpload::InProgress { uploaded_len: 0 });
+        }
+
+        let meta = ExternalCallMeta::try_from_account(external_call_meta)?;
+        if !matches!(
+            meta,
+            ExternalCallMeta::Accumulation { .. } | ExternalCallMeta::Transferred { .. }
+        ) {
+            return Err(Error::ExternalStorageWrongState);
+        }
+
+        let storage_data = self.account_infos[EXTERNAL_CALL_STORAGE_INDEX]
+            .try_borrow_data()
+            .map_err(|_| Error::AccountBorrowFailing)?;
+        let stored_external_call = storage_data
+            .get(EXTERNAL_CALL_STORAGE_HEADER_LEN..)
+            .filter(|stored| stored.len() == external_call.len())
+            .ok_or(Error::ExternalCallLengthMismatch)?;
+
+        match meta {
+            ExternalCallMeta::Accumulation {
+                external_call_len: uploaded_len,
+            } => {
+                let uploaded_len = usize::try_from(uploaded_len)
+                    .ok()
+                    .filter(|uploaded_len| *uploaded_len <= external_call.len())
+                    .ok_or(Error::ExternalCallLengthMismatch)?;
+
+                if stored_external_call[..uploaded_len] != external_call[..uploaded_len] {
+                    return Err(Error::ExternalCallPrefixMismatch);
+                }
+
+                if uploaded_len == external_call.len() {
+                    Ok(ExternalCallUpload::Completed)
+                } else {
+                    Ok(ExternalCallUpload::InProgress {
+                        uploaded_len: uploaded_len as u64,
+                    })
+                }
+            }
+            _ => {
+                if stored_external_call != external_call {
+                    return Err(Error::ExternalCallDataMismatch);
+                }
+
+                Ok(ExternalCallUpload::Completed)
+            }
+        }
+    }
+
+    /// Same as [`get_nonce`]
+    pub fn get_nonce(&self) -> Result<u64, Error> {
+        get_nonce(self.account_infos)
+    }
+
+    /// Same as [`get_bridge`]
+    pub fn get_bridge(&self) -> Result<Bridge, Error> {
+        get_bridge(self.account_infos)
+    }
+
+    /// Same as [`get_chain_support_info`]
+    pub fn get_chain_support_info(
+        &self,
+        target_chain_id: [u8; 32],
+    ) -> Result<ChainSupportInfo, Error> {
+        self.check_chain_support_info_account(target_chain_id)
+            .and_then(|()| get_account_by_index(self.account_infos, CHAIN_SUPPORT_INFO_INDEX))
+    }
+
+    /// Same as [`check_chain_support_info_account`]
+    pub fn check_chain_support_info_account(&self, target_chain_id: [u8; 32]) -> Result<(), Error> {
+        self.account_infos
+            .get(CHAIN_SUPPORT_INFO_INDEX)
+            .ok_or(Error::WrongAccountIndex)
+            .and_then(|chain_support_info| {
+                self.bumps
+                    .chain_support_info_address(&target_chain_id)?
+                    .eq(chain_support_info.key)
+                    .then_some(())
+                    .ok_or(Error::WrongChainSupportInfo)
+            })
+    }
+
+    /// Same as [`get_asset_fee_info`]
+    pub fn get_asset_fee_info(&self, target_chain_id: [u8; 32]) -> Result<AssetFeeInfo, Error> {
+        self.account_infos
+            .get(TOKEN_MINT_INDEX)
+            .zip(self.account_infos.get(ASSET_FEE_INDEX))
+            .ok_or(Error::WrongAccountIndex)
+            .and_then(|(token_mint, asset_fee)| {
+                self.bumps
+                    .asset_fee_info_address(
+                        &self.bumps.bridge_address(token_mint.key)?,
+                        &target_chain_id,
+                    )?
+                    .eq(asset_fee.key)
+                    .then_some(())
+                    .ok_or(Error::WrongBridgeFeeInfo)
+            })
+            .and_then(|()| get_account_by_index(self.account_infos, ASSET_FEE_INDEX))
+    }
+
+    /// Same as [`is_chain_supported`]
+    pub fn is_chain_supported(&self, target_chain_id: [u8; 32]) -> Result<bool, Error> {
+        Ok(match self.get_chain_support_info(target_chain_id)? {
+            ChainSupportInfo::Supported { .. } => true,
+            ChainSupportInfo::NotSupported => false,
+        })
+    }
+
+    /// Same as [`get_chain_address_len`]
+    pub fn get_chain_address_len(&self, target_chain_id: [u8; 32]) -> Result<u16, Error> {
+        match self.get_chain_support_info(target_chain_id)? {
+            ChainSupportInfo::NotSupported => Err(Error::TargetChainNotSupported),
+            ChainSupportInfo::Supported {
+                chain_address_len, ..
+            } => Ok(chain_address_len),
+        }
+    }
+
+    /// Same as [`check_send_addresses_len`]
+    pub fn check_send_addresses_len(&self, send_ix: &SendIx) -> Result<(), Error> {
+        let chain_address_len = usize::from(self.get_chain_address_len(send_ix.target_chain_id)?);
+
+        if send_ix.receiver.len() != chain_address_len {
+            msg!(
+                "Expected receiver len: {}, Actual: {}",
+                chain_address_len,
+                send_ix.receiver.len()
+            );
+            return Err(Error::WrongReceiverAddressLength);
+        }
+
+        match &send_ix.submission_params {
+            Some(params) if params.fallback_address.len() != chain_address_len => {
+                msg!(
+                    "Expected fallback address len: {}, Actual: {}",
+                    chain_address_len,
+                    params.fallback_address.len()
+                );
+                Err(Error::WrongFallbackAddressLength)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Same as [`check_send_destination_accounts`]
+    pub fn check_send_destination_accounts(&self, send_ix: &SendIx) -> Result<(), Error> {
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+
+        self.check_chain_support_info_account(send_ix.target_chain_id)?;
+
+        let asset_fee = accounts.asset_fee().key;
+        if DEFAULT_BRIDGE_FEE_ADDRESS.ne(asset_fee)
+            && self
+                .bumps
+                .asset_fee_info_address(accounts.bridge().key, &send_ix.target_chain_id)?
+                .ne(asset_fee)
+        {
+            msg!("Unexpected asset fee account: {}", asset_fee);
+            return Err(Error::WrongBridgeFeeInfo);
+        }
+
+        let external_call_storage = self.bumps.external_call_storage_address(
+            &get_external_call_shortcut(send_ix),
+            accounts.send_from().key,
+        )?;
+        check_account_key(
+            accounts.external_call_storage(),
+            &external_call_storage,
+            Error::WrongExternalCallStorage,
+        )?;
+        check_account_key(
+            accounts.external_call_meta(),
+            &self
+                .bumps
+                .external_call_meta_address(&external_call_storage)?,
+            Error::WrongExternalCallMeta,
+        )
+    }
+
+    /// Same as [`preflight_send`]
+    pub fn preflight_send(&self, send_ix: &SendIx) -> Result<(), Error> {
+        if let Status::Paused = get_state(self.account_infos)?.status {
+            return Err(Error::ProtocolPaused);
+        }
+
+        let bridge = get_bridge(self.account_infos)?;
+        if let BridgeState::Paused = bridge.state {
+            return Err(Error::BridgePaused);
+        }
+
+        self.check_send_addresses_len(send_ix)?;
+
+        if send_ix.amount > bridge.max_amount {
+            msg!(
+                "Bridge max amount: {}, Actual: {}",
+                bridge.max_amount,
+                send_ix.amount
+            );
+            return Err(Error::AmountExceedsBridgeMax);
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`check_fee_limits`]
+    pub fn check_fee_limits(&self, send_ix: &SendIx, fee_limits: &FeeLimits) -> Result<(), Error> {
+        let discount = self.get_discount()?;
+        if send_ix.is_use_asset_fee {
+            let asset_fix_fee =
+                self.discounted_asset_fix_fee(send_ix.target_chain_id, &discount)?;
+            if asset_fix_fee > fee_limits.max_asset_fix_fee {
+                msg!(
+                    "Max asset fix fee: {}, Actual: {}",
+                    fee_limits.max_asset_fix_fee,
+                    asset_fix_fee
+                );
+                return Err(Error::AssetFixFeeExceedsLimit);
+            }
+        } else {
+            let native_fix_fee =
+                self.discounted_native_fix_fee(send_ix.target_chain_id, &discount)?;
+            if native_fix_fee > fee_limits.max_native_fix_fee {
+                msg!(
+                    "Max native fix fee: {}, Actual: {}",
+                    fee_limits.max_native_fix_fee,
+                    native_fix_fee
+                );
+                return Err(Error::NativeFixFeeExceedsLimit);
+            }
+        }
+
+        let transfer_fee_bps = self.discounted_transfer_fee(send_ix.target_chain_id, &discount)?;
+        if transfer_fee_bps > fee_limits.max_transfer_fee_bps {
+            msg!(
+                "Max transfer fee bps: {}, Actual: {}",
+                fee_limits.max_transfer_fee_bps,
+                transfer_fee_bps
+            );
+            return Err(Error::TransferFeeExceedsLimit);
+        }
+
+        let received_amount = self
+            .subtract_all_discounted_fees(
+                send_ix.target_chain_id,
+                send_ix.amount,
+                send_ix
+                    .submission_params
+                    .as_ref()
+                    .map(|params| params.execution_fee)
+                    .unwrap_or(0),
+                send_ix.is_use_asset_fee,
+                &discount,
+            )
+            .map_err(|err| match err {
+                Error::AmountTooSmallToCoverFees => Error::ReceivedAmountBelowMinimum,
+                err => err,
+            })?;
+        if received_amount < fee_limits.min_received_amount {
+            msg!(
+                "Min received amount: {}, Actual: {}",
+                fee_limits.min_received_amount,
+                received_amount
+            );
+            return Err(Error::ReceivedAmountBelowMinimum);
+        }
+
+        Ok(())
+    }
+
+    /// Same as [`get_transfer_fee`]
+    pub fn get_transfer_fee(&self, target_chain_id: [u8; 32]) -> Result<u64, Error> {
+        self.discounted_transfer_fee(target_chain_id, &self.get_discount()?)
+    }
+
+    fn undiscounted_transfer_fee(&self, target_chain_id: [u8; 32]) -> Result<u64, Error> {
+        self.get_transfer_fee_for_chain(target_chain_id)
+            .and_then(|chain_fee| {
+                chain_fee
+                    .map(Ok)
+                    .unwrap_or_else(|| Ok(get_state(self.account_infos)?.global_transfer_fee_bps))
+            })
+    }
+
+    fn discounted_transfer_fee(
+        &self,
+        target_chain_id: [u8; 32],
+        discount: &DiscountInfo,
+    ) -> Result<u64, Error> {
+        apply_discount(
+            self.undiscounted_transfer_fee(target_chain_id)?,
+            discount.transfer_bps,
+        )
+    }
+
+    /// Same as [`get_transfer_fee_for_chain`]
+    pub fn get_transfer_fee_for_chain(
+        &self,
+        target_chain_id: [u8; 32],
+    ) -> Result<Option<u64>, Error> {
+        self.get_chain_support_info(target_chain_id)
+            .and_then(|chain_support_info| match chain_support_info {
+                ChainSupportInfo::NotSupported => Err(Error::TargetChainNotSupported),
+                ChainSupportInfo::Supported {
+                    transfer_fee_bps, ..
+                } => Ok(transfer_fee_bps),
+            })
+    }
+
+    /// Same as [`get_chain_native_fix_fee`]
+    pub fn get_chain_native_fix_fee(&self, target_chain_id: [u8; 32]) -> Result<u64, Error> {
+        self.discounted_native_fix_fee(target_chain_id, &self.get_discount()?)
+    }
+
+    fn undiscounted_native_fix_fee(&self, target_chain_id: [u8; 32]) -> Result<u64, Error> {
+        match self.get_chain_support_info(target_chain_id)? {
+            ChainSupportInfo::NotSupported => get_default_native_fix_fee(self.account_infos),
+            ChainSupportInfo::Supported { fixed_fee, .. } => fixed_fee
+                .map(Ok)
+                .unwrap_or_else(|| get_default_native_fix_fee(self.account_infos)),
+        }
+    }
+
+    fn discounted_native_fix_fee(
+        &self,
+        target_chain_id: [u8; 32],
+        discount: &DiscountInfo,
+    ) -> Result<u64, Error> {
+        apply_discount(
+            self.undiscounted_native_fix_fee(target_chain_id)?,
+            discount.fix_bps,
+        )
+    }
+
+    /// Same as [`get_default_native_fix_fee`]
+    pub fn get_default_native_fix_fee(&self) -> Result<u64, Error> {
+        get_default_native_fix_fee(self.account_infos)
+    }
+
+    /// Same as [`is_asset_fee_available`]
+    pub fn is_asset_fee_available(&self, target_chain_id: [u8; 32]) -> Result<bool, Error> {
+        match self.get_asset_fee_info(target_chain_id) {
+            Ok(asset_fee) => Ok(asset_fee.asset_chain_fee.is_some()),
+            Err(err) if err == Error::WrongAccountIndex => Err(err),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Same as [`try_get_chain_asset_fix_fee`]
+    pub fn try_get_chain_asset_fix_fee(&self, target_chain_id: [u8; 32]) -> Result<u64, Error> {
+        self.discounted_asset_fix_fee(target_chain_id, &self.get_discount()?)
+    }
+
+    fn undiscounted_asset_fix_fee(&self, target_chain_id: [u8; 32]) -> Result<u64, Error> {
+        self.get_asset_fee_info(target_chain_id)?
+            .asset_chain_fee
+            .ok_or(Error::AssetFeeNotSupported)
+    }
+
+    fn discounted_asset_fix_fee(
+        &self,
+        target_chain_id: [u8; 32],
+        discount: &DiscountInfo,
+    ) -> Result<u64, Error> {
+        apply_discount(
+            self.undiscounted_asset_fix_fee(target_chain_id)?,
+            discount.fix_bps,
+        )
+    }
+
+    /// Same as [`add_all_fees`]
+    pub fn add_all_fees(
+        &self,
+        target_chain_id: [u8; 32],
+        exact_amount: u64,
+        execution_fee: u64,
+        is_use_asset_fee: bool,
+    ) -> Result<u64, Error> {
+        self.add_all_discounted_fees(
+            target_chain_id,
+            exact_amount,
+            execution_fee,
+            is_use_asset_fee,
+            &self.get_discount()?,
+        )
+    }
+
+    fn add_all_discounted_fees(
+        &self,
+        target_chain_id: [u8; 32],
+        exact_amount: u64,
+        execution_fee: u64,
+        is_use_asset_fee: bool,
+        discount: &DiscountInfo,
+    ) -> Result<u64, Error> {
+        token::add_token_transfer_fee(
+            get_token_mint(self.account_infos)?,
+            add_transfer_fee_bps(
+                exact_amount
+                    .checked_add(execution_fee)
+                    .ok_or(OVERFLOW_ERR)?
+                    .checked_add(
+                        is_use_asset_fee
+                            .then(|| self.discounted_asset_fix_fee(target_chain_id, discount))
+                            .transpose()?
+                            .unwrap_or(0),
+                    )
+                    .ok_or(OVERFLOW_ERR)?,
+                self.discounted_transfer_fee(target_chain_id, discount)?,
+            )?,
+        )
+    }
+
+    /// Same as [`add_transfer_fee`]
+    pub fn add_transfer_fee(
+        &self,
+        target_chain_id: [u8; 32],
+        exact_amount: u64,
+    ) -> Result<u64, Error> {
+        add_transfer_fee_bps(exact_amount, self.get_transfer_fee(target_chain_id)?)
+    }
+
+    /// Same as [`subtract_all_fees`]
+    pub fn subtract_all_fees(
+        &self,
+        target_chain_id: [u8; 32],
+        amount: u64,
+        execution_fee: u64,
+        is_use_asset_fee: bool,
+    ) -> Result<u64, Error> {
+        self.subtract_all_discounted_fees(
+            target_chain_id,
+            amount,
+            execution_fee,
+            is_use_asset_fee,
+            &self.get_discount()?,
+        )
+    }
+
+    fn subtract_all_discounted_fees(
+        &self,
+        target_chain_id: [u8; 32],
+        amount: u64,
+        execution_fee: u64,
+        is_use_asset_fee: bool,
+        discount: &DiscountInfo,
+    ) -> Result<u64, Error> {
+        self.discounted_received_amount(target_chain_id, amount, is_use_asset_fee, discount)?
+            .checked_sub(execution_fee)
+            .ok_or(Error::AmountTooSmallToCoverFees)
+    }
+
+    /// Same as [`received_amount_for`]
+    pub fn received_amount_for(
+        &self,
+        target_chain_id: [u8; 32],
+        amount: u64,
+        is_use_asset_fee: bool,
+    ) -> Result<u64, Error> {
+        self.discounted_received_amount(
+            target_chain_id,
+            amount,
+            is_use_asset_fee,
+            &self.get_discount()?,
+        )
+    }
+
+    fn discounted_received_amount(
+        &self,
+        target_chain_id: [u8; 32],
+        amount: u64,
+        is_use_asset_fee: bool,
+        discount: &DiscountInfo,
+    ) -> Result<u64, Error> {
+        subtract_transfer_fee_bps(
+            token::subtract_token_transfer_fee(get_token_mint(self.account_infos)?, amount)?,
+            self.discounted_transfer_fee(target_chain_id, discount)?,
+        )?
+        .checked_sub(
+            is_use_asset_fee
+                .then(|| self.discounted_asset_fix_fee(target_chain_id, discount))
+                .transpose()?
+                .unwrap_or(0),
+        )
+        .ok_or(Error::AmountTooSmallToCoverFees)
+    }
+
+    /// Same as [`subtract_transfer_fee`]
+    pub fn subtract_transfer_fee(
+        &self,
+        target_chain_id: [u8; 32],
+        amount: u64,
+    ) -> Result<u64, Error> {
+        subtract_transfer_fee_bps(amount, self.get_transfer_fee(target_chain_id)?)
+    }
+
+    /// Same as [`quote_send`]
+    pub fn quote_send(
+        &self,
+        target_chain_id: [u8; 32],
+        quote_amount: QuoteAmount,
+        execution_fee: u64,
+        is_use_asset_fee: bool,
+        external_call_len: usize,
+    ) -> Result<FeeQuote, Error> {
+        self.quote_discounted_send(
+            target_chain_id,
+            quote_amount,
+            execution_fee,
+            is_use_asset_fee,
+            external_call_len,
+            &self.get_discount()?,
+        )
+    }
+
+    fn quote_discounted_send(
+        &self,
+        target_chain_id: [u8; 32],
+        quote_amount: QuoteAmount,
+        execution_fee: u64,
+        is_use_asset_fee: bool,
+        external_call_len: usize,
+        discount: &DiscountInfo,
+    ) -> Result<FeeQuote, Error> {
+        let (native_fix_fee, asset_fix_fee) = if is_use_asset_fee {
+            (0, self.discounted_asset_fix_fee(target_chain_id, discount)?)
+        } else {
+            (
+                self.discounted_native_fix_fee(target_chain_id, discount)?,
+                0,
+            )
+        };
+        let transfer_fee_bps = self.discounted_transfer_fee(target_chain_id, discount)?;
+        let external_call_rent = match external_call_len {
+            0 => 0,
+            len => estimator::get_external_call_rent(len)?,
+        };
+        let fix_fees = execution_fee
+            .checked_add(asset_fix_fee)
+            .ok_or(OVERFLOW_ERR)?;
+
+        let token_mint = get_token_mint(self.account_infos)?;
+
+        let (amount, received_amount, transfer_fee, token_transfer_fee) = match quote_amount {
+            QuoteAmount::ExactAmount(received_amount) => {
+                let amount_without_transfer_fee =
+                    received_amount.checked_add(fix_fees).ok_or(OVERFLOW_ERR)?;
+                let amount_without_token_transfer_fee =
+                    add_transfer_fee_bps(amount_without_transfer_fee, transfer_fee_bps)?;
+                let amount =
+                    token::add_token_transfer_fee(token_mint, amount_without_token_transfer_fee)?;
+                (
+                    amount,
+                    received_amount,
+                    amount_without_token_transfer_fee - amount_without_transfer_fee,
+                    amount - amount_without_token_transfer_fee,
+                )
+            }
+            QuoteAmount::Amount(amount) => {
+                let amount_without_token_transfer_fee =
+                    token::subtract_token_transfer_fee(token_mint, amount)?;
+                let amount_without_transfer_fee =
+                    subtract_transfer_fee_bps(amount_without_token_transfer_fee, transfer_fee_bps)?;
+                let received_amount = amount_without_transfer_fee
+                    .checked_sub(fix_fees)
+                    .ok_or(Error::AmountTooSmallToCoverFees)?;
+                (
+                    amount,
+                    received_amount,
+                    amount_without_token_transfer_fee - amount_without_transfer_fee,
+                    amount - amount_without_token_transfer_fee,
+                )
+            }
+        };
+
+        Ok(FeeQuote {
+            native_fix_fee,
+            asset_fix_fee,
+            transfer_fee,
+            token_transfer_fee,
+            execution_fee,
+            external_call_rent,
+            amount,
+            received_amount,
+        })
+    }
+
+    /// Same as [`invoke_debridge_send`]
+    pub fn invoke_debridge_send(&self, send_ix: SendIx) -> ProgramResult {
+        self.invoke_debridge_send_signed(send_ix, &[])
+    }
+
+    /// Same as [`invoke_debridge_send_signed`]
+    pub fn invoke_debridge_send_signed(
+        &self,
+        send_ix: SendIx,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.invoke_send_instruction(&send_ix, signers_seeds)
+    }
+
+    fn invoke_send_instruction(
+        &self,
+        send_ix: &SendIx,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.check_send_addresses_len(send_ix)?;
+
+        let ix = send_instruction(
+            send_ix,
+            self.account_infos.iter().map(|account| account.key),
+        )?;
+
+        invoke_signed(&ix, self.account_infos, signers_seeds)
+    }
+
+    /// Same as [`invoke_debridge_send_with_receipt`]
+    pub fn invoke_debridge_send_with_receipt(
+        &self,
+        send_ix: SendIx,
+    ) -> Result<SendReceipt, ProgramError> {
+        self.invoke_debridge_send_with_receipt_signed(send_ix, &[])
+    }
+
+    /// Same as [`invoke_debridge_send_with_receipt_signed`]
+    pub fn invoke_debridge_send_with_receipt_signed(
+        &self,
+        send_ix: SendIx,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, ProgramError> {
+        let nonce = self.get_nonce()?;
+        let external_call_shortcut = get_external_call_shortcut(&send_ix);
+
+        self.invoke_send_instruction(&send_ix, signers_seeds)?;
+
+        let submission_id = match get_return_data()
+            .filter(|(program_id, _)| program_id.eq(&DEBRIDGE_ID))
+            .and_then(|(_, data)| parse_send_return_data(&data))
+        {
+            Some((submission_id, returned_nonce)) if returned_nonce == nonce => Some(submission_id),
+            _ => self.calculate_submission_id(&send_ix, nonce)?,
+        };
+
+        Ok(SendReceipt {
+            submission_id,
+            nonce,
+            external_call_shortcut,
+        })
+    }
+
+    /// Same as [`calculate_submission_id`]
+    pub fn calculate_submission_id(
+        &self,
+        send_ix: &SendIx,
+        nonce: u64,
+    ) -> Result<Option<[u8; 32]>, Error> {
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+
+        if token::get_mint_authority(accounts.token_mint())?.as_ref()
+            == Some(accounts.mint_authority().key)
+        {
+            return Ok(None);
+        }
+
+        let debridge_id = SolanaKeccak256::hash(
+            [
+                SOLANA_CHAIN_ID.as_slice(),
+                accounts.token_mint().key.as_ref(),
+            ]
+            .concat()
+            .as_slice(),
+        );
+        let execution_fee = send_ix
+            .submission_params
+            .as_ref()
+            .map(|params| params.execution_fee)
+            .unwrap_or(0);
+        let received_amount = self.subtract_all_fees(
+            send_ix.target_chain_id,
+            send_ix.amount,
+            execution_fee,
+            send_ix.is_use_asset_fee,
+        )?;
+
+        let mut packed_submission = [
+            SUBMISSION_PREFIX.as_slice(),
+            &debridge_id,
+            &SOLANA_CHAIN_ID,
+            &send_ix.target_chain_id,
+            &u256_be_bytes(received_amount),
+            &send_ix.receiver,
+            &u256_be_bytes(nonce),
+        ]
+        .concat();
+
+        if let Some(params) = send_ix.submission_params.as_ref() {
+            packed_submission.extend_from_slice(&u256_be_bytes(params.execution_fee));
+            packed_submission.extend_from_slice(&params.flags);
+            packed_submission.extend_from_slice(&SolanaKeccak256::hash(&params.fallback_address));
+            packed_submission.extend_from_slice(&params.external_call_shortcut);
+            packed_submission
+                .extend_from_slice(&SolanaKeccak256::hash(accounts.send_from().key.as_ref()));
+        }
+
+        Ok(Some(SolanaKeccak256::hash(&packed_submission)))
+    }
+
+    /// Same as [`invoke_debridge_send_with_fee_limits`]
+    pub fn invoke_debridge_send_with_fee_limits(
+        &self,
+        send_ix: SendIx,
+        fee_limits: &FeeLimits,
+    ) -> Result<SendReceipt, ProgramError> {
+        self.invoke_debridge_send_with_fee_limits_signed(send_ix, fee_limits, &[])
+    }
+
+    /// Same as [`invoke_debridge_send_with_fee_limits_signed`]
+    pub fn invoke_debridge_send_with_fee_limits_signed(
+        &self,
+        send_ix: SendIx,
+        fee_limits: &FeeLimits,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, ProgramError> {
+        self.check_fee_limits(&send_ix, fee_limits)?;
+
+        self.invoke_debridge_send_with_receipt_signed(send_ix, signers_seeds)
+    }
+
+    /// Same as [`invoke_debridge_send_checked`]
+    pub fn invoke_debridge_send_checked(
+        &self,
+        send_ix: SendIx,
+        fee_quote: &FeeQuote,
+    ) -> Result<SendReceipt, ProgramError> {
+        self.invoke_debridge_send_checked_signed(send_ix, fee_quote, &[])
+    }
+
+    /// Same as [`invoke_debridge_send_checked_signed`]
+    pub fn invoke_debridge_send_checked_signed(
+        &self,
+        send_ix: SendIx,
+        fee_quote: &FeeQuote,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, ProgramError> {
+        if send_ix.amount != fee_quote.amount {
+            msg!(
+                "Send amount: {}, Fee quote amount: {}",
+                send_ix.amount,
+                fee_quote.amount
+            );
+            return Err(Error::SendAmountMismatchesFeeQuote.into());
+        }
+
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+        let send_from = accounts.send_from();
+        let send_from_wallet = accounts.send_from_wallet();
+
+        let lamports_before = send_from.lamports();
+        let tokens_before = token::get_token_account_amount(send_from_wallet)?;
+
+        let receipt = self.invoke_debridge_send_with_receipt_signed(send_ix, signers_seeds)?;
+
+        check_debited(
+            lamports_before,
+            send_from.lamports(),
+            fee_quote.native_fix_fee,
+            Error::UnexpectedLamportsDebited,
+        )?;
+        check_debited(
+            tokens_before,
+            token::get_token_account_amount(send_from_wallet)?,
+            fee_quote.amount,
+            Error::UnexpectedTokensDebited,
+        )?;
+
+        Ok(receipt)
+    }
+
+    /// Same as [`invoke_init_external_call`]
+    pub fn invoke_init_external_call(&self, external_call: &[u8]) -> Result<(), ProgramError> {
+        self.invoke_init_external_call_signed(external_call, &[])
+    }
+
+    /// Same as [`invoke_init_external_call_signed`]
+    pub fn invoke_init_external_call_signed(
+        &self,
+        external_call: &[u8],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), ProgramError> {
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+        let external_call_storage = accounts.external_call_storage().clone();
+        let external_call_meta = accounts.external_call_meta().clone();
+        let send_from = accounts.send_from().clone();
+        let system_program = accounts.system_program().clone();
+        let debridge_program = accounts.debridge_program().clone();
+
+        let external_call_shortcut = SolanaKeccak256::hash(external_call);
+
+        if external_call_meta.owner.ne(&DEBRIDGE_ID) {
+            invoke_signed(
+                &init_external_call_instruction(
+                    external_call.len(),
+                    external_call_shortcut,
+                    external_call,
+                    external_call_storage.key,
+                    external_call_meta.key,
+                    send_from.key,
+                    system_program.key,
+                )?,
+                &[
+                    external_call_storage,
+                    external_call_meta,
+                    send_from,
+                    system_program,
+                    debridge_program,
+                ],
+                signers_seeds,
+            )?;
+
+            return Ok(());
+        }
+
+        match self.get_external_call_upload_state(external_call)? {
+            ExternalCallUpload::Completed => Ok(()),
+            ExternalCallUpload::InProgress { uploaded_len } => invoke_signed(
+                &update_external_call_instruction(
+                    external_call_shortcut,
+                    uploaded_len,
+                    &external_call[uploaded_len as usize..],
+                    external_call_storage.key,
+                    external_call_meta.key,
+                    send_from.key,
+                )?,
+                &[
+                    external_call_storage,
+                    external_call_meta,
+                    send_from,
+                    debridge_program,
+                ],
+                signers_seeds,
+            ),
+        }
+    }
+
+    /// Same as [`invoke_update_external_call`]
+    pub fn invoke_update_external_call(
+        &self,
+        external_call_shortcut: [u8; 32],
+        offset: u64,
+        chunk: &[u8],
+    ) -> Result<(), ProgramError> {
+        self.invoke_update_external_call_signed(external_call_shortcut, offset, chunk, &[])
+    }
+
+    /// Same as [`invoke_update_external_call_signed`]
+    pub fn invoke_update_external_call_signed(
+        &self,
+        external_call_shortcut: [u8; 32],
+        offset: u64,
+        chunk: &[u8],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), ProgramError> {
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+        let external_call_storage = accounts.external_call_storage().clone();
+        let external_call_meta = accounts.external_call_meta().clone();
+        let send_from = accounts.send_from().clone();
+        let debridge_program = accounts.debridge_program().clone();
+
+        invoke_signed(
+            &update_external_call_instruction(
+                external_call_shortcut,
+                offset,
+                chunk,
+                external_call_storage.key,
+                external_call_meta.key,
+                send_from.key,
+            )?,
+            &[
+                external_call_storage,
+                external_call_meta,
+                send_from,
+                debridge_program,
+            ],
+            signers_seeds,
+        )
+    }
+
+    /// Same as [`invoke_upload_external_call_chunk`]
+    pub fn invoke_upload_external_call_chunk(
+        &self,
+        external_call: &[u8],
+        max_chunk_len: usize,
+    ) -> Result<ExternalCallUpload, ProgramError> {
+        self.invoke_upload_external_call_chunk_signed(external_call, max_chunk_len, &[])
+    }
+
+    /// Same as [`invoke_upload_external_call_chunk_signed`]
+    pub fn invoke_upload_external_call_chunk_signed(
+        &self,
+        external_call: &[u8],
+        max_chunk_len: usize,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<ExternalCallUpload, ProgramError> {
+        if max_chunk_len == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+        let external_call_storage = accounts.external_call_storage().clone();
+        let external_call_meta = accounts.external_call_meta().clone();
+        let send_from = accounts.send_from().clone();
+        let system_program = accounts.system_program().clone();
+        let debridge_program = accounts.debridge_program().clone();
+
+        let is_initialized = external_call_meta.owner.eq(&DEBRIDGE_ID);
+        let offset = match self.get_external_call_upload_state(external_call)? {
+            ExternalCallUpload::Completed => return Ok(ExternalCallUpload::Completed),
+            ExternalCallUpload::InProgress { uploaded_len } => uploaded_len as usize,
+        };
+
+        let end = external_call
+            .len()
+            .min(offset.saturating_add(max_chunk_len));
+        let external_call_shortcut = SolanaKeccak256::hash(external_call);
+
+        if !is_initialized {
+            invoke_signed(
+                &init_external_call_instruction(
+                    external_call.len(),
+                    external_call_shortcut,
+                    &external_call[..end],
+                    external_call_storage.key,
+                    external_call_meta.key,
+                    send_from.key,
+                    system_program.key,
+                )?,
+                &[
+                    external_call_storage,
+                    external_call_meta,
+                    send_from,
+                    system_program,
+                    debridge_program,
+                ],
+                signers_seeds,
+            )?;
+        } else {
+            invoke_signed(
+                &update_external_call_instruction(
+                    external_call_shortcut,
+                    offset as u64,
+                    &external_call[offset..end],
+                    external_call_storage.key,
+                    external_call_meta.key,
+                    send_from.key,
+                )?,
+                &[
+                    external_call_storage,
+                    external_call_meta,
+                    send_from,
+                    debridge_program,
+                ],
+                signers_seeds,
+            )?;
+        }
+
+        Ok(if end == external_call.len() {
+            ExternalCallUpload::Completed
+        } else {
+            ExternalCallUpload::InProgress {
+                uploaded_len: end as u64,
+            }
+        })
+    }
+
+    /// Same as [`invoke_close_external_call`]
+    pub fn invoke_close_external_call(
+        &self,
+        external_call_shortcut: [u8; 32],
+    ) -> Result<(), ProgramError> {
+        self.invoke_close_external_call_signed(external_call_shortcut, &[])
+    }
+
+    /// Same as [`invoke_close_external_call_signed`]
+    pub fn invoke_close_external_call_signed(
+        &self,
+        external_call_shortcut: [u8; 32],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), ProgramError> {
+        let accounts = SendAccounts::try_from(self.account_infos)?;
+
+        invoke_close_external_call_signed(
+            external_call_shortcut,
+            accounts.external_call_storage(),
+            accounts.external_call_meta(),
+            accounts.send_from(),
+            accounts.debridge_program(),
+            signers_seeds,
+        )
+    }
+
+    /// Same as [`invoke_send_message`]
+    pub fn invoke_send_message(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+    ) -> Result<(), InvokeError> {
+        self.send_message_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            execution_fee,
+            fallback_address,
+            referral_code,
+            None,
+            &[],
+        )
+    }
+
+    /// Same as [`invoke_send_message_signed`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_message_signed(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), InvokeError> {
+        self.send_message_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            execution_fee,
+            fallback_address,
+            referral_code,
+            None,
+            signers_seeds,
+        )
+    }
+
+    /// Same as [`invoke_send_message_with_fee_limits`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_message_with_fee_limits(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+        fee_limits: &FeeLimits,
+    ) -> Result<(), InvokeError> {
+        self.send_message_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            execution_fee,
+            fallback_address,
+            referral_code,
+            Some(fee_limits),
+            &[],
+        )
+    }
+
+    /// Same as [`invoke_send_message_with_fee_limits_signed`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_message_with_fee_limits_signed(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+        fee_limits: &FeeLimits,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), InvokeError> {
+        self.send_message_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            execution_fee,
+            fallback_address,
+            referral_code,
+            Some(fee_limits),
+            signers_seeds,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn send_message_signed(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+        fee_limits: Option<&FeeLimits>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), InvokeError> {
+        let send_ix = SendIx {
+            target_chain_id,
+            receiver,
+            is_use_asset_fee: false,
+            amount: self.add_all_fees(target_chain_id, 0, execution_fee, false)?,
+            submission_params: Some(SendSubmissionParamsInput::with_message(
+                external_call.clone(),
+                execution_fee,
+                fallback_address,
+            )),
+            referral_code,
+        };
+        if let Some(fee_limits) = fee_limits {
+            self.check_fee_limits(&send_ix, fee_limits)?;
+        }
+
+        self.invoke_init_external_call_signed(external_call.as_slice(), signers_seeds)?;
+        self.invoke_debridge_send_signed(send_ix, signers_seeds)?;
+
+        Ok(())
+    }
+
+    /// Same as [`invoke_send_with_external_call`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_with_external_call(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        exact_amount: u64,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        flags: [u8; 32],
+        referral_code: Option<u32>,
+    ) -> Result<SendReceipt, InvokeError> {
+        self.send_with_external_call_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            exact_amount,
+            execution_fee,
+            fallback_address,
+            flags,
+            referral_code,
+            None,
+            &[],
+        )
+    }
+
+    /// Same as [`invoke_send_with_external_call_signed`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_with_external_call_signed(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        exact_amount: u64,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        flags: [u8; 32],
+        referral_code: Option<u32>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, InvokeError> {
+        self.send_with_external_call_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            exact_amount,
+            execution_fee,
+            fallback_address,
+            flags,
+            referral_code,
+            None,
+            signers_seeds,
+        )
+    }
+
+    /// Same as [`invoke_send_with_external_call_with_fee_limits`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_with_external_call_with_fee_limits(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        exact_amount: u64,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        flags: [u8; 32],
+        referral_code: Option<u32>,
+        fee_limits: &FeeLimits,
+    ) -> Result<SendReceipt, InvokeError> {
+        self.send_with_external_call_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            exact_amount,
+            execution_fee,
+            fallback_address,
+            flags,
+            referral_code,
+            Some(fee_limits),
+            &[],
+        )
+    }
+
+    /// Same as [`invoke_send_with_external_call_with_fee_limits_signed`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_with_external_call_with_fee_limits_signed(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        exact_amount: u64,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        flags: [u8; 32],
+        referral_code: Option<u32>,
+        fee_limits: &FeeLimits,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, InvokeError> {
+        self.send_with_external_call_signed(
+            external_call,
+            target_chain_id,
+            receiver,
+            exact_amount,
+            execution_fee,
+            fallback_address,
+            flags,
+            referral_code,
+            Some(fee_limits),
+            signers_seeds,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn send_with_external_call_signed(
+        &self,
+        external_call: Vec<u8>,
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        exact_amount: u64,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        flags: [u8; 32],
+        referral_code: Option<u32>,
+        fee_limits: Option<&FeeLimits>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, InvokeError> {
+        let send_ix = SendIx {
+            target_chain_id,
+            receiver,
+            is_use_asset_fee: false,
+            amount: self.add_all_fees(target_chain_id, exact_amount, execution_fee, false)?,
+            submission_params: Some(SendSubmissionParamsInput::with_external_call(
+                external_call.clone(),
+                execution_fee,
+                fallback_address,
+                flags,
+            )),
+            referral_code,
+        };
+        if let Some(fee_limits) = fee_limits {
+            self.check_fee_limits(&send_ix, fee_limits)?;
+        }
+
+        self.invoke_init_external_call_signed(external_call.as_slice(), signers_seeds)?;
+        Ok(self.invoke_debridge_send_with_receipt_signed(send_ix, signers_seeds)?)
+    }
+
+    /// Same as [`invoke_send_hashed_message`]
+    pub fn invoke_send_hashed_message(
+        &self,
+        external_call_shortcut: [u8; 32],
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+    ) -> Result<SendReceipt, InvokeError> {
+        self.invoke_send_hashed_message_signed(
+            external_call_shortcut,
+            target_chain_id,
+            receiver,
+            execution_fee,
+            fallback_address,
+            referral_code,
+            &[],
+        )
+    }
+
+    /// Same as [`invoke_send_hashed_message_signed`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn invoke_send_hashed_message_signed(
+        &self,
+        external_call_shortcut: [u8; 32],
+        target_chain_id: [u8; 32],
+        receiver: Vec<u8>,
+        execution_fee: u64,
+        fallback_address: Vec<u8>,
+        referral_code: Option<u32>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<SendReceipt, InvokeError> {
+        let mut flags = [0; 32];
+        flags.set_revert_if_external_call();
+        flags.set_proxy_with_sender();
+
+        let send_ix = SendIx {
+            target_chain_id,
+            receiver,
+            is_use_asset_fee: false,
+            amount: self.add_all_fees(target_chain_id, 0, execution_fee, false)?,
+            submission_params: Some(SendSubmissionParamsInput::with_hashed_external_call(
+                external_call_shortcut,
+                execution_fee,
+                fallback_address,
+                flags,
+            )),
+            referral_code,
+        };
+
+        Ok(self.invoke_debridge_send_with_receipt_signed(send_ix, signers_seeds)?)
+    }
+}
+
+pub const BRIDGE_INDEX: usize = 0;
+pub const TOKEN_MINT_INDEX: usize = 1;
+pub const STAKING_WALLET_INDEX: usize = 2;
+pub const MINT_AUTHORITY_INDEX: usize = 3;
+pub const CHAIN_SUPPORT_INFO_INDEX: usize = 4;
+pub const SETTINGS_PROGRAM_INDEX: usize = 5;
+pub const TOKEN_PROGRAM_INDEX: usize = 6;
+pub const STATE_INDEX: usize = 7;
+pub const FEE_BENEFICIARY_INDEX: usize = 8;
+pub const NONCE_STORAGE_INDEX: usize = 9;
+pub const SEND_FROM_WALLET_INDEX: usize = 10;
+pub const SYSTEM_PROGRAM_INDEX: usize = 11;
+pub const EXTERNAL_CALL_STORAGE_INDEX: usize = 12;
+pub const EXTERNAL_CALL_META_INDEX: usize = 13;
+pub const SEND_FROM_INDEX: usize = 14;
+pub const DISCOUNT_INDEX: usize = 15;
+pub const ASSET_FEE_INDEX: usize = 16;
+pub const DEBRIDGE_PROGRAM_INDEX: usize = 17;
+
+pub(crate) struct MetaTemplate {
+    pub(crate) is_signer: bool,
+    pub(crate) is_writable: bool,
+}
+
+pub(crate) const SEND_META_TEMPLATE: [MetaTemplate; 18] = [
+    // 0: Bridge
+    MetaTemplate {
+        is_signer: false,
+        is_writable: true,
+    },
+    // 1: Token Mint
     MetaTemplate {
         is_signer: false,
         is_writable: true,
@@ -715,7 +3674,7 @@ const SEND_META_TEMPLATE: [MetaTemplate; 18] = [
         is_signer: false,
         is_writable: false,
     },
-    // 6: Spl token program
+    // 6: Spl token or Token-2022 program
     MetaTemplate {
         is_signer: false,
         is_writable: false,
@@ -775,4 +3734,1853 @@ const SEND_META_TEMPLATE: [MetaTemplate; 18] = [
         is_signer: false,
         is_writable: false,
     },
-];
\ No newline at end of file
+];
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use borsh::BorshSerialize;
+    use proptest::prelude::*;
+    use solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        program_stubs::{set_syscall_stubs, SyscallStubs},
+        system_program,
+    };
+
+    use crate::{
+        debridge_accounts::{ChainSupportInfo, DiscountInfo},
+        flags::{CheckReservedFlag, SetReservedFlag},
+        hash::HashAdapter,
+        keys::{
+            AssetFeeInfoPubkey, AssociatedTokenPubkey, BridgePubkey, ChainSupportInfoPubkey,
+            DiscountInfoPubkey, ExternalCallMetaPubkey, ExternalCallStoragePubkey,
+            MintAuthorityPubkey, NonceStoragePubkey, StatePubkey,
+        },
+        sending::{
+            add_all_fees, add_transfer_fee_bps, build_init_external_call_instruction,
+            build_send_instruction, build_update_external_call_instruction, bumps::SendBumps,
+            calculate_submission_id, check_debited, check_fee_limits, check_send_addresses_len,
+            close_external_call_instruction, get_chain_address_len, get_chain_native_fix_fee,
+            get_discount, get_external_call_upload_state, get_transfer_fee,
+            init_external_call_instruction, invoke_close_external_call, invoke_debridge_send_batch,
+            invoke_debridge_send_checked, invoke_init_external_call, invoke_send_hashed_message,
+            invoke_send_message_with_fee_limits, invoke_send_with_external_call,
+            invoke_send_with_external_call_with_fee_limits, invoke_upload_external_call_chunk,
+            parse_send_return_data, preflight_send, quote_send, received_amount_for,
+            send_instruction, send_instruction_data, send_instruction_data_len, subtract_all_fees,
+            subtract_transfer_fee_bps, try_get_chain_asset_fix_fee,
+            update_external_call_instruction, validate_send_accounts, ExternalCallUpload,
+            FeeLimits, FeeQuote, InitExternalCallIx, QuoteAmount, SendAccounts, SendIx,
+            SendIxBuilder, SendReceipt, SendSubmissionParamsInput,
+            SendSubmissionParamsInputBuilder, UpdateExternalCallIx, ASSET_FEE_INDEX, BRIDGE_INDEX,
+            CHAIN_SUPPORT_INFO_INDEX, DEBRIDGE_PROGRAM_INDEX, DISCOUNT_INDEX,
+            EXTERNAL_CALL_META_INDEX, EXTERNAL_CALL_STORAGE_HEADER_LEN,
+            EXTERNAL_CALL_STORAGE_INDEX, FEE_BENEFICIARY_INDEX, INIT_EXTERNAL_CALL_DISCRIMINATOR,
+            MINT_AUTHORITY_INDEX, NONCE_STORAGE_INDEX, SEND_BATCH_GROUP_LEN, SEND_DISCRIMINATOR,
+            SEND_FROM_INDEX, SEND_FROM_WALLET_INDEX, STATE_INDEX, SYSTEM_PROGRAM_INDEX,
+            TOKEN_MINT_INDEX, TOKEN_PROGRAM_INDEX, UPDATE_EXTERNAL_CALL_DISCRIMINATOR,
+        },
+        Error, Pubkey, SolanaKeccak256, BPS_DENOMINATOR, DEBRIDGE_ID, DEFAULT_REFERRAL_CODE,
+        POLYGON_CHAIN_ID, SETTINGS_ID, SOLANA_CHAIN_ID, SPL_TOKEN_2022_ID, SPL_TOKEN_ID,
+    };
+
+    fn golden_send_ix() -> SendIx {
+        SendIx {
+            target_chain_id: [13; 32],
+            receiver: vec![14; 32],
+            is_use_asset_fee: false,
+            amount: 1000,
+            submission_params: Some(SendSubmissionParamsInput {
+                execution_fee: 100,
+                flags: [1; 32],
+                fallback_address: vec![15; 32],
+                external_call_shortcut: [16; 32],
+            }),
+            referral_code: Some(2000),
+        }
+    }
+
+    fn golden_send_ix_data() -> Vec<u8> {
+        vec![
+            102, 251, 20, 187, 65, 75, 12, 69, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13,
+            13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 32, 0, 0,
+            0, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14,
+            14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 0, 232, 3, 0, 0, 0, 0, 0, 0, 1, 100, 0, 0,
+            0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+            1, 1, 1, 1, 1, 1, 1, 1, 32, 0, 0, 0, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15,
+            15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 16, 16,
+            16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16,
+            16, 16, 16, 16, 16, 16, 16, 16, 1, 208, 7, 0, 0,
+        ]
+    }
+
+    #[test]
+    fn test_send_ix_consistency() {
+        assert_eq!(
+            SEND_DISCRIMINATOR
+                .into_iter()
+                .chain(golden_send_ix().try_to_vec().expect("Unreachable"))
+                .collect::<Vec<u8>>(),
+            golden_send_ix_data()
+        )
+    }
+
+    #[test]
+    fn test_build_send_instruction() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let metas = keys
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect::<Vec<_>>();
+
+        let ix = build_send_instruction(golden_send_ix(), &metas).expect("Failed to build ix");
+
+        assert_eq!(ix.program_id, DEBRIDGE_ID);
+        assert_eq!(ix.data, golden_send_ix_data());
+        assert!(ix.accounts[SEND_FROM_INDEX].is_signer);
+        assert!(ix.accounts[STATE_INDEX].is_writable);
+        assert_eq!(
+            send_instruction(&golden_send_ix(), keys.iter()).expect("Failed to build ix"),
+            ix
+        );
+
+        assert_eq!(
+            build_send_instruction(golden_send_ix(), &metas[..17]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn test_build_init_external_call_instruction() {
+        let keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let metas = keys
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect::<Vec<_>>();
+
+        let ix =
+            build_init_external_call_instruction(&[1, 2, 3], &metas).expect("Failed to build ix");
+
+        assert_eq!(
+            ix.data,
+            vec![
+                82, 77, 58, 138, 145, 157, 41, 253, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 115, 111, 108, 241, 136, 94,
+                218, 84, 183, 160, 83, 49, 140, 212, 30, 32, 147, 34, 13, 171, 21, 214, 83, 129,
+                177, 21, 122, 54, 51, 168, 59, 253, 92, 146, 57, 3, 0, 0, 0, 1, 2, 3
+            ]
+        );
+        assert_eq!(
+            ix.accounts
+                .iter()
+                .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+                .collect::<Vec<_>>(),
+            vec![
+                (keys[EXTERNAL_CALL_STORAGE_INDEX], false, true),
+                (keys[EXTERNAL_CALL_META_INDEX], false, true),
+                (keys[SEND_FROM_INDEX], true, true),
+                (keys[SYSTEM_PROGRAM_INDEX], false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_update_external_call_instruction() {
+        let keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let metas = keys
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect::<Vec<_>>();
+
+        let ix = build_update_external_call_instruction([7; 32], 5, &[1, 2, 3], &metas)
+            .expect("Failed to build ix");
+
+        assert_eq!(
+            ix.data,
+            vec![
+                38, 234, 80, 194, 91, 237, 107, 143, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 115, 111, 108, 7, 7, 7, 7, 7, 7, 7, 7, 7,
+                7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 5, 0, 0, 0, 0,
+                0, 0, 0, 3, 0, 0, 0, 1, 2, 3
+            ]
+        );
+        assert_eq!(
+            ix.accounts
+                .iter()
+                .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+                .collect::<Vec<_>>(),
+            vec![
+                (keys[EXTERNAL_CALL_STORAGE_INDEX], false, true),
+                (keys[EXTERNAL_CALL_META_INDEX], false, true),
+                (keys[SEND_FROM_INDEX], true, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instruction_data_is_exactly_sized() {
+        let short_send_ix = SendIx {
+            receiver: vec![14; 20],
+            submission_params: None,
+            referral_code: None,
+            ..golden_send_ix()
+        };
+        for send_ix in [golden_send_ix(), short_send_ix] {
+            let data = send_instruction_data(&send_ix).expect("Failed to serialize");
+            let referral_code = send_ix.referral_code.or(DEFAULT_REFERRAL_CODE);
+            assert_eq!(
+                data.len(),
+                send_instruction_data_len(&send_ix, referral_code)
+            );
+            assert_eq!(
+                data,
+                [
+                    SEND_DISCRIMINATOR.as_slice(),
+                    &SendIx {
+                        referral_code,
+                        ..send_ix
+                    }
+                    .try_to_vec()
+                    .expect("Unreachable"),
+                ]
+                .concat()
+            );
+        }
+
+        let external_call = [3; 100];
+        let keys = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let ix = init_external_call_instruction(
+            1000,
+            [7; 32],
+            &external_call,
+            &keys[0],
+            &keys[1],
+            &keys[2],
+            &keys[3],
+        )
+        .expect("Failed to build ix");
+        assert_eq!(ix.data.len(), ix.data.capacity());
+        assert_eq!(
+            ix.data,
+            [
+                INIT_EXTERNAL_CALL_DISCRIMINATOR.as_slice(),
+                &InitExternalCallIx {
+                    external_call_len: 1000,
+                    chain_id: SOLANA_CHAIN_ID,
+                    external_call_shortcut: [7; 32],
+                    external_call: external_call.to_vec(),
+                }
+                .try_to_vec()
+                .expect("Unreachable"),
+            ]
+            .concat()
+        );
+
+        let ix = update_external_call_instruction(
+            [7; 32],
+            100,
+            &external_call,
+            &keys[0],
+            &keys[1],
+            &keys[2],
+        )
+        .expect("Failed to build ix");
+        assert_eq!(ix.data.len(), ix.data.capacity());
+        assert_eq!(
+            ix.data,
+            [
+                UPDATE_EXTERNAL_CALL_DISCRIMINATOR.as_slice(),
+                &UpdateExternalCallIx {
+                    chain_id: SOLANA_CHAIN_ID,
+                    external_call_shortcut: [7; 32],
+                    offset: 100,
+                    external_call: external_call.to_vec(),
+                }
+                .try_to_vec()
+                .expect("Unreachable"),
+            ]
+            .concat()
+        );
+    }
+
+    #[test]
+    fn test_invoke_close_external_call() {
+        let mut keys = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[3] = DEBRIDGE_ID;
+        let meta_discriminator = [52, 154, 212, 31, 208, 203, 151, 253];
+        let close = |meta_state: &[u8]| {
+            let data = vec![vec![], [meta_discriminator.as_slice(), meta_state].concat()];
+            with_owned_account_infos(&keys, data, &DEBRIDGE_ID, |account_infos| {
+                record_invokes(|| {
+                    invoke_close_external_call(
+                        [7; 32],
+                        &account_infos[0],
+                        &account_infos[1],
+                        &account_infos[2],
+                        &account_infos[3],
+                    )
+                })
+            })
+        };
+
+        assert_eq!(
+            close(&[3]),
+            (
+                Ok(()),
+                vec![
+                    close_external_call_instruction([7; 32], &keys[0], &keys[1], &keys[2])
+                        .expect("Failed to build ix")
+                ]
+            )
+        );
+        assert_eq!(
+            close(&[0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            (Err(Error::ExternalStorageWrongState.into()), vec![])
+        );
+    }
+
+    #[test]
+    fn test_upload_external_call_chunk() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call = [1; 10];
+        let upload = |meta_data: Option<Vec<u8>>| {
+            let mut data = vec![vec![]; 18];
+            let owner = match meta_data {
+                Some(meta_data) => {
+                    data[EXTERNAL_CALL_META_INDEX] = meta_data;
+                    data[EXTERNAL_CALL_STORAGE_INDEX] =
+                        [[0; 8].as_slice(), &external_call].concat();
+                    DEBRIDGE_ID
+                }
+                None => Pubkey::new_unique(),
+            };
+
+            with_owned_account_infos(&keys, data, &owner, |account_infos| {
+                record_invokes(|| {
+                    invoke_upload_external_call_chunk(&external_call, 4, account_infos)
+                })
+            })
+        };
+        let metas = keys
+            .iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect::<Vec<_>>();
+        let update = |offset: usize, end: usize| {
+            build_update_external_call_instruction(
+                SolanaKeccak256::hash(&external_call),
+                offset as u64,
+                &external_call[offset..end],
+                &metas,
+            )
+            .expect("Failed to build ix")
+        };
+        let accumulation_data = |uploaded_len: u64| {
+            [
+                vec![52, 154, 212, 31, 208, 203, 151, 253, 0],
+                uploaded_len.to_le_bytes().to_vec(),
+            ]
+            .concat()
+        };
+
+        assert_eq!(
+            upload(None),
+            (
+                Ok(ExternalCallUpload::InProgress { uploaded_len: 4 }),
+                vec![init_external_call_instruction(
+                    external_call.len(),
+                    SolanaKeccak256::hash(&external_call),
+                    &external_call[..4],
+                    &keys[EXTERNAL_CALL_STORAGE_INDEX],
+                    &keys[EXTERNAL_CALL_META_INDEX],
+                    &keys[SEND_FROM_INDEX],
+                    &keys[SYSTEM_PROGRAM_INDEX],
+                )
+                .expect("Failed to build ix")]
+            )
+        );
+        assert_eq!(
+            upload(Some(accumulation_data(4))),
+            (
+                Ok(ExternalCallUpload::InProgress { uploaded_len: 8 }),
+                vec![update(4, 8)]
+            )
+        );
+        assert_eq!(
+            upload(Some(accumulation_data(8))),
+            (Ok(ExternalCallUpload::Completed), vec![update(8, 10)])
+        );
+        assert_eq!(
+            upload(Some(accumulation_data(10))),
+            (Ok(ExternalCallUpload::Completed), vec![])
+        );
+        assert_eq!(
+            upload(Some(accumulation_data(11))),
+            (Err(Error::ExternalCallLengthMismatch.into()), vec![])
+        );
+    }
+
+    #[test]
+    fn test_external_call_upload_state() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call = [1, 2, 3, 4, 5, 6];
+        let upload_state = |meta_data: Vec<u8>, stored: &[u8]| {
+            let mut data = vec![vec![]; 18];
+            data[EXTERNAL_CALL_META_INDEX] = meta_data;
+            data[EXTERNAL_CALL_STORAGE_INDEX] = [[0; 8].as_slice(), stored].concat();
+
+            with_owned_account_infos(&keys, data, &DEBRIDGE_ID, |account_infos| {
+                assert_eq!(
+                    invoke_init_external_call(&external_call, account_infos),
+                    get_external_call_upload_state(&external_call, account_infos)
+                        .map(|_| ())
+                        .map_err(ProgramError::from)
+                );
+                get_external_call_upload_state(&external_call, account_infos)
+            })
+        };
+        let meta_prefix = [52, 154, 212, 31, 208, 203, 151, 253];
+        let accumulation_data =
+            |uploaded_len: u64| [&meta_prefix[..], &[0], &uploaded_len.to_le_bytes()].concat();
+        let transferred_data = [&meta_prefix[..], &[2], &7i64.to_le_bytes()].concat();
+
+        assert_eq!(
+            upload_state(accumulation_data(3), &[1, 2, 3, 0, 0, 0]),
+            Ok(ExternalCallUpload::InProgress { uploaded_len: 3 })
+        );
+        assert_eq!(
+            upload_state(accumulation_data(3), &[1, 9, 3, 0, 0, 0]),
+            Err(Error::ExternalCallPrefixMismatch)
+        );
+        assert_eq!(
+            upload_state(accumulation_data(3), &[1, 2, 3, 0, 0]),
+            Err(Error::ExternalCallLengthMismatch)
+        );
+        assert_eq!(
+            upload_state(transferred_data.clone(), &external_call),
+            Ok(ExternalCallUpload::Completed)
+        );
+        assert_eq!(
+            upload_state(transferred_data, &[1, 2, 3, 4, 5, 7]),
+            Err(Error::ExternalCallDataMismatch)
+        );
+        assert_eq!(
+            upload_state([&meta_prefix[..], &[3]].concat(), &external_call),
+            Err(Error::ExternalStorageWrongState)
+        );
+    }
+
+    #[test]
+    fn test_external_call_storage_layout() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call = [1, 2, 3, 4, 5, 6];
+        // Synthetic account data built from the assumed layout: discriminator header
+        // followed by raw external call bytes. No dump of a deBridge external call
+        // storage account is vendored in the repo, so this doesn't prove the on-chain
+        // layout and has to be replaced with real account data once one is captured
+        let storage_fixture = [
+            0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 1, 2, 3, 4, 5, 6,
+        ];
+        let upload_state = |storage_data: &[u8]| {
+            let mut data = vec![vec![]; 18];
+            data[EXTERNAL_CALL_META_INDEX] = [
+                &[52, 154, 212, 31, 208, 203, 151, 253, 0],
+                &6u64.to_le_bytes()[..],
+            ]
+            .concat();
+            data[EXTERNAL_CALL_STORAGE_INDEX] = storage_data.to_vec();
+
+            with_owned_account_infos(&keys, data, &DEBRIDGE_ID, |account_infos| {
+                get_external_call_upload_state(&external_call, account_infos)
+            })
+        };
+
+        assert_eq!(
+            storage_fixture.len(),
+            EXTERNAL_CALL_STORAGE_HEADER_LEN + external_call.len()
+        );
+        assert_eq!(
+            upload_state(&storage_fixture),
+            Ok(ExternalCallUpload::Completed)
+        );
+        assert_eq!(
+            upload_state(&external_call),
+            Err(Error::ExternalCallLengthMismatch)
+        );
+        assert_eq!(
+            upload_state(&storage_fixture[..storage_fixture.len() - 1]),
+            Err(Error::ExternalCallLengthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_with_hashed_external_call() {
+        let params = SendSubmissionParamsInput::with_hashed_external_call(
+            [7; 32],
+            100,
+            vec![1; 20],
+            [0; 32],
+        );
+
+        assert_eq!(params.external_call_shortcut, [7; 32]);
+        assert!(params.flags.check_send_hashed_data());
+        assert!(!params.flags.check_proxy_with_sender());
+    }
+
+    #[test]
+    fn test_send_ix_builder() {
+        let params = SendSubmissionParamsInputBuilder::default()
+            .execution_fee(100)
+            .fallback_address(vec![2; 20])
+            .external_call(&[1, 2, 3])
+            .build()
+            .expect("Failed to build params");
+        assert_eq!(
+            params.external_call_shortcut,
+            SolanaKeccak256::hash(&[1, 2, 3])
+        );
+        assert_eq!(params.flags, [0; 32]);
+
+        let send_ix = SendIxBuilder::default()
+            .target_chain_id(POLYGON_CHAIN_ID)
+            .receiver(vec![1; 20])
+            .amount(1000)
+            .submission_params(params.clone())
+            .build()
+            .expect("Failed to build send ix");
+        assert!(!send_ix.is_use_asset_fee);
+        assert_eq!(send_ix.referral_code, None);
+
+        assert!(SendSubmissionParamsInputBuilder::default()
+            .fallback_address(vec![])
+            .build()
+            .is_err());
+        let mut flags = [0; 32];
+        flags.set_send_hashed_data();
+        assert!(SendSubmissionParamsInputBuilder::default()
+            .fallback_address(vec![2; 20])
+            .flags(flags)
+            .build()
+            .is_err());
+
+        assert!(SendIxBuilder::default()
+            .target_chain_id(POLYGON_CHAIN_ID)
+            .receiver(vec![1; 32])
+            .amount(1000)
+            .submission_params(params.clone())
+            .build()
+            .is_err());
+        assert!(SendIxBuilder::default()
+            .target_chain_id(POLYGON_CHAIN_ID)
+            .receiver(vec![1; 20])
+            .amount(10)
+            .submission_params(params)
+            .build()
+            .is_err());
+        assert!(SendIxBuilder::default()
+            .target_chain_id(POLYGON_CHAIN_ID)
+            .receiver(vec![1; 20])
+            .build()
+            .is_err());
+
+        let (keys, data) = fee_accounts();
+        with_account_infos(&keys, data, |account_infos| {
+            assert!(send_ix.clone().validate_against(account_infos).is_ok());
+
+            let asset_fee_send_ix = SendIx {
+                is_use_asset_fee: true,
+                ..send_ix.clone()
+            };
+            assert!(asset_fee_send_ix.validate_against(account_infos).is_ok());
+
+            let wrong_receiver_send_ix = SendIx {
+                receiver: vec![1; 32],
+                ..send_ix.clone()
+            };
+            assert_eq!(
+                wrong_receiver_send_ix.validate_against(account_infos).err(),
+                Some(Error::WrongReceiverAddressLength)
+            );
+
+            let other_chain_send_ix = SendIx {
+                target_chain_id: [9; 32],
+                ..send_ix
+            };
+            assert_eq!(
+                other_chain_send_ix.validate_against(account_infos).err(),
+                Some(Error::WrongChainSupportInfo)
+            );
+        });
+    }
+
+    #[test]
+    fn test_parse_send_return_data() {
+        assert_eq!(
+            parse_send_return_data(&[[3; 32].as_slice(), &7_u64.to_le_bytes()].concat()),
+            Some(([3; 32], 7))
+        );
+        assert_eq!(parse_send_return_data(&[3; 39]), None);
+    }
+
+    #[test]
+    fn test_calculate_submission_id() {
+        let (keys, mut data) = fee_accounts();
+        let u256 = |value: u8| [[0; 31].as_slice(), &[value]].concat();
+        let mut flags = [0; 32];
+        flags.set_revert_if_external_call();
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: false,
+            // Transfer fee of 10 bps rounds down to zero, so 200 tokens are received
+            amount: 200,
+            submission_params: None,
+            referral_code: None,
+        };
+        let with_params_send_ix = SendIx {
+            amount: 300,
+            submission_params: Some(SendSubmissionParamsInput::with_external_call(
+                vec![7; 4],
+                100,
+                vec![2; 20],
+                flags,
+            )),
+            ..send_ix.clone()
+        };
+        let packed_submission = [
+            u256(1),
+            SolanaKeccak256::hash(&[SOLANA_CHAIN_ID, keys[TOKEN_MINT_INDEX].to_bytes()].concat())
+                .to_vec(),
+            SOLANA_CHAIN_ID.to_vec(),
+            POLYGON_CHAIN_ID.to_vec(),
+            u256(200),
+            vec![1; 20],
+            u256(5),
+        ]
+        .concat();
+
+        with_account_infos(&keys, data.clone(), |account_infos| {
+            assert_eq!(
+                calculate_submission_id(account_infos, &send_ix, 5),
+                Ok(Some(SolanaKeccak256::hash(&packed_submission)))
+            );
+            assert_eq!(
+                calculate_submission_id(account_infos, &with_params_send_ix, 5),
+                Ok(Some(SolanaKeccak256::hash(
+                    &[
+                        packed_submission,
+                        u256(100),
+                        flags.to_vec(),
+                        SolanaKeccak256::hash(&[2; 20]).to_vec(),
+                        SolanaKeccak256::hash(&[7; 4]).to_vec(),
+                        SolanaKeccak256::hash(keys[SEND_FROM_INDEX].as_ref()).to_vec(),
+                    ]
+                    .concat()
+                )))
+            );
+        });
+
+        data[TOKEN_MINT_INDEX][..4].copy_from_slice(&1_u32.to_le_bytes());
+        data[TOKEN_MINT_INDEX][4..36].copy_from_slice(keys[MINT_AUTHORITY_INDEX].as_ref());
+        with_account_infos(&keys, data, |account_infos| {
+            assert_eq!(
+                calculate_submission_id(account_infos, &send_ix, 5),
+                Ok(None)
+            );
+        });
+    }
+
+    #[test]
+    fn test_invoke_debridge_send_batch() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call_storage = Pubkey::find_external_call_storage_address(
+            &SolanaKeccak256::hash(&[]),
+            &keys[SEND_FROM_INDEX],
+        )
+        .0;
+        let group = [
+            Pubkey::find_chain_support_info_address(&POLYGON_CHAIN_ID)
+                .unwrap()
+                .0,
+            Pubkey::default_bridge_fee_address().unwrap().0,
+            external_call_storage,
+            Pubkey::find_external_call_meta_address(&external_call_storage).0,
+        ];
+        keys.extend(group);
+        keys.extend(group);
+        keys[18 + SEND_BATCH_GROUP_LEN + 2] = Pubkey::new_unique();
+
+        let chain_support_info = chain_support_info_data(ChainSupportInfo::Supported {
+            fixed_fee: None,
+            transfer_fee_bps: None,
+            chain_address_len: 20,
+        });
+        keys[DISCOUNT_INDEX] = Pubkey::find_no_discount_address().0;
+        let mut data = vec![vec![]; keys.len()];
+        // Token wrapped by debridge, so submission id isn't calculated
+        data[TOKEN_MINT_INDEX] = [
+            [1, 0, 0, 0].as_slice(),
+            keys[MINT_AUTHORITY_INDEX].as_ref(),
+            &[0; 46],
+        ]
+        .concat();
+        data[STATE_INDEX] = state_data(&Pubkey::new_unique(), 10000, 10);
+        data[NONCE_STORAGE_INDEX] = [
+            [116, 193, 37, 78, 49, 76, 253, 53].as_slice(),
+            &5_u64.to_le_bytes(),
+        ]
+        .concat();
+        data[18] = chain_support_info.clone();
+        data[18 + SEND_BATCH_GROUP_LEN] = chain_support_info;
+
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: false,
+            amount: 1000,
+            submission_params: None,
+            referral_code: None,
+        };
+
+        with_account_infos(&keys, data.clone(), |account_infos| {
+            assert_eq!(
+                count_invokes(|| invoke_debridge_send_batch(
+                    &[send_ix.clone(), send_ix.clone()],
+                    account_infos
+                )),
+                (Err(Error::WrongExternalCallStorage.into()), 0)
+            );
+            assert_eq!(
+                count_invokes(|| invoke_debridge_send_batch(
+                    std::slice::from_ref(&send_ix),
+                    account_infos
+                )),
+                (Err(Error::WrongAccountIndex.into()), 0)
+            );
+        });
+
+        keys[18 + SEND_BATCH_GROUP_LEN + 2] = external_call_storage;
+        with_account_infos(&keys, data, |account_infos| {
+            let receipt = SendReceipt {
+                submission_id: None,
+                nonce: 5,
+                external_call_shortcut: SolanaKeccak256::hash(&[]),
+            };
+            assert_eq!(
+                count_invokes(|| invoke_debridge_send_batch(
+                    &[send_ix.clone(), send_ix.clone()],
+                    account_infos
+                )),
+                (Ok(vec![receipt, receipt]), 2)
+            );
+        });
+    }
+
+    #[test]
+    fn test_invoke_send_with_external_call() {
+        let (mut keys, mut data) = fee_accounts();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call = vec![1, 2, 3];
+        keys[EXTERNAL_CALL_STORAGE_INDEX] = Pubkey::find_external_call_storage_address(
+            &SolanaKeccak256::hash(&external_call),
+            &keys[SEND_FROM_INDEX],
+        )
+        .0;
+        keys[EXTERNAL_CALL_META_INDEX] =
+            Pubkey::find_external_call_meta_address(&keys[EXTERNAL_CALL_STORAGE_INDEX]).0;
+        data[NONCE_STORAGE_INDEX] = [
+            [116, 193, 37, 78, 49, 76, 253, 53].as_slice(),
+            &5_u64.to_le_bytes(),
+        ]
+        .concat();
+        let mut flags = [0; 32];
+        flags.set_revert_if_external_call();
+
+        with_account_infos(&keys, data, |account_infos| {
+            let send_ix = SendIx {
+                target_chain_id: POLYGON_CHAIN_ID,
+                receiver: vec![1; 20],
+                is_use_asset_fee: false,
+                amount: add_all_fees(account_infos, POLYGON_CHAIN_ID, 1000, 100, false).unwrap(),
+                submission_params: Some(SendSubmissionParamsInput::with_external_call(
+                    external_call.clone(),
+                    100,
+                    vec![2; 20],
+                    flags,
+                )),
+                referral_code: None,
+            };
+            assert_eq!(
+                invoke_send_with_external_call(
+                    external_call.clone(),
+                    POLYGON_CHAIN_ID,
+                    vec![1; 20],
+                    1000,
+                    100,
+                    vec![2; 20],
+                    flags,
+                    None,
+                    account_infos,
+                )
+                .map_err(ProgramError::from),
+                Ok(SendReceipt {
+                    submission_id: calculate_submission_id(account_infos, &send_ix, 5).unwrap(),
+                    nonce: 5,
+                    external_call_shortcut: SolanaKeccak256::hash(&external_call),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_send_with_fee_limits() {
+        let (mut keys, mut data) = fee_accounts();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call = vec![1, 2, 3];
+        keys[EXTERNAL_CALL_STORAGE_INDEX] = Pubkey::find_external_call_storage_address(
+            &SolanaKeccak256::hash(&external_call),
+            &keys[SEND_FROM_INDEX],
+        )
+        .0;
+        keys[EXTERNAL_CALL_META_INDEX] =
+            Pubkey::find_external_call_meta_address(&keys[EXTERNAL_CALL_STORAGE_INDEX]).0;
+        data[NONCE_STORAGE_INDEX] = [
+            [116, 193, 37, 78, 49, 76, 253, 53].as_slice(),
+            &5_u64.to_le_bytes(),
+        ]
+        .concat();
+        let exceeded_limits = FeeLimits {
+            max_native_fix_fee: 9999,
+            ..FeeLimits::default()
+        };
+
+        with_account_infos(&keys, data, |account_infos| {
+            let send_message = |fee_limits: &FeeLimits| {
+                count_invokes(|| {
+                    invoke_send_message_with_fee_limits(
+                        external_call.clone(),
+                        POLYGON_CHAIN_ID,
+                        vec![1; 20],
+                        100,
+                        vec![2; 20],
+                        None,
+                        fee_limits,
+                        account_infos,
+                    )
+                    .map_err(ProgramError::from)
+                })
+            };
+            let send_with_external_call = |fee_limits: &FeeLimits| {
+                count_invokes(|| {
+                    invoke_send_with_external_call_with_fee_limits(
+                        external_call.clone(),
+                        POLYGON_CHAIN_ID,
+                        vec![1; 20],
+                        1000,
+                        100,
+                        vec![2; 20],
+                        [0; 32],
+                        None,
+                        fee_limits,
+                        account_infos,
+                    )
+                    .map(|receipt| receipt.nonce)
+                    .map_err(ProgramError::from)
+                })
+            };
+
+            assert_eq!(send_message(&FeeLimits::default()), (Ok(()), 2));
+            assert_eq!(
+                send_message(&exceeded_limits),
+                (Err(Error::NativeFixFeeExceedsLimit.into()), 0)
+            );
+            assert_eq!(send_with_external_call(&FeeLimits::default()), (Ok(5), 2));
+            assert_eq!(
+                send_with_external_call(&exceeded_limits),
+                (Err(Error::NativeFixFeeExceedsLimit.into()), 0)
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_debited() {
+        let check = |before, after, expected| {
+            check_debited(before, after, expected, Error::UnexpectedLamportsDebited)
+        };
+
+        assert_eq!(check(10000, 2500, 7500), Ok(()));
+        assert_eq!(check(10000, 10000, 0), Ok(()));
+        assert_eq!(
+            check(10000, 2500, 7499),
+            Err(Error::UnexpectedLamportsDebited)
+        );
+        assert_eq!(
+            check(10000, 2500, 7501),
+            Err(Error::UnexpectedLamportsDebited)
+        );
+        assert_eq!(check(2500, 10000, 0), Err(Error::UnexpectedLamportsDebited));
+    }
+
+    #[test]
+    fn test_invoke_debridge_send_checked() {
+        let (mut keys, mut data) = fee_accounts();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        keys[EXTERNAL_CALL_STORAGE_INDEX] = Pubkey::find_external_call_storage_address(
+            &SolanaKeccak256::hash(&[]),
+            &keys[SEND_FROM_INDEX],
+        )
+        .0;
+        keys[EXTERNAL_CALL_META_INDEX] =
+            Pubkey::find_external_call_meta_address(&keys[EXTERNAL_CALL_STORAGE_INDEX]).0;
+        data[NONCE_STORAGE_INDEX] = [
+            [116, 193, 37, 78, 49, 76, 253, 53].as_slice(),
+            &5_u64.to_le_bytes(),
+        ]
+        .concat();
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: false,
+            amount: 1000,
+            submission_params: None,
+            referral_code: None,
+        };
+        let send_checked = |wallet_data: Vec<u8>, fee_quote: FeeQuote| {
+            let mut data = data.clone();
+            data[SEND_FROM_WALLET_INDEX] = wallet_data;
+            let send_ix = SendIx {
+                amount: fee_quote.amount,
+                ..send_ix.clone()
+            };
+            with_account_infos(&keys, data, |account_infos| {
+                invoke_debridge_send_checked(send_ix, &fee_quote, account_infos)
+                    .map(|receipt| receipt.nonce)
+            })
+        };
+        let mut wallet_data = vec![0; 165];
+        wallet_data[64..72].copy_from_slice(&1000_u64.to_le_bytes());
+
+        // Off-chain CPI doesn't change balances, so only zero debits match
+        assert_eq!(
+            send_checked(wallet_data.clone(), FeeQuote::default()),
+            Ok(5)
+        );
+        assert_eq!(
+            send_checked(
+                wallet_data.clone(),
+                FeeQuote {
+                    amount: 1000,
+                    ..FeeQuote::default()
+                }
+            ),
+            Err(Error::UnexpectedTokensDebited.into())
+        );
+        assert_eq!(
+            send_checked(
+                wallet_data,
+                FeeQuote {
+                    native_fix_fee: 10000,
+                    ..FeeQuote::default()
+                }
+            ),
+            Err(Error::UnexpectedLamportsDebited.into())
+        );
+        assert_eq!(
+            send_checked(vec![], FeeQuote::default()),
+            Err(Error::AccountDeserializeError.into())
+        );
+        with_account_infos(&keys, data, |account_infos| {
+            assert_eq!(
+                count_invokes(|| {
+                    invoke_debridge_send_checked(send_ix, &FeeQuote::default(), account_infos)
+                        .map(|receipt| receipt.nonce)
+                }),
+                (Err(Error::SendAmountMismatchesFeeQuote.into()), 0)
+            );
+        });
+    }
+
+    thread_local! {
+        static INVOKED: RefCell<Vec<Instruction>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Syscall stubs recording CPIs of the current thread
+    struct RecordingStubs;
+
+    impl SyscallStubs for RecordingStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+    }
+
+    pub(super) fn record_invokes<R>(f: impl FnOnce() -> R) -> (R, Vec<Instruction>) {
+        set_syscall_stubs(Box::new(RecordingStubs));
+        INVOKED.with(|invoked| invoked.borrow_mut().clear());
+        let result = f();
+
+        (result, INVOKED.with(RefCell::take))
+    }
+
+    fn count_invokes<R>(f: impl FnOnce() -> R) -> (R, usize) {
+        let (result, invoked) = record_invokes(f);
+
+        (result, invoked.len())
+    }
+
+    pub(super) fn with_account_infos<R>(
+        keys: &[Pubkey],
+        data: Vec<Vec<u8>>,
+        f: impl FnOnce(&[AccountInfo]) -> R,
+    ) -> R {
+        with_owned_account_infos(keys, data, &Pubkey::new_unique(), f)
+    }
+
+    fn with_owned_account_infos<R>(
+        keys: &[Pubkey],
+        mut data: Vec<Vec<u8>>,
+        owner: &Pubkey,
+        f: impl FnOnce(&[AccountInfo]) -> R,
+    ) -> R {
+        let mut lamports = vec![0; keys.len()];
+        data.resize(keys.len(), vec![]);
+        let account_infos = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+            })
+            .collect::<Vec<_>>();
+
+        f(&account_infos)
+    }
+
+    fn state_data(
+        fee_beneficiary: &Pubkey,
+        global_fixed_fee: u64,
+        global_transfer_fee_bps: u64,
+    ) -> Vec<u8> {
+        let mut state_data = vec![216, 146, 107, 94, 104, 75, 182, 177, 0];
+        state_data.extend([0; 64]);
+        state_data.extend(fee_beneficiary.to_bytes());
+        state_data.extend([0; 33]);
+        state_data.extend(global_fixed_fee.to_le_bytes());
+        state_data.extend(global_transfer_fee_bps.to_le_bytes());
+        state_data
+    }
+
+    fn chain_support_info_data(chain_support_info: ChainSupportInfo) -> Vec<u8> {
+        [
+            vec![175, 59, 40, 127, 55, 33, 200, 203],
+            chain_support_info.try_to_vec().expect("Unreachable"),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_send_accounts_view() {
+        let keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+
+        with_account_infos(&keys, vec![], |account_infos| {
+            assert_eq!(
+                SendAccounts::try_from(&account_infos[..17]).err(),
+                Some(Error::WrongAccountIndex)
+            );
+
+            let send_accounts = SendAccounts::try_from(account_infos).expect("Unreachable");
+            assert_eq!(send_accounts.bridge().key, &keys[0]);
+            assert_eq!(send_accounts.token_mint().key, &keys[1]);
+            assert_eq!(send_accounts.state().key, &keys[STATE_INDEX]);
+            assert_eq!(send_accounts.send_from().key, &keys[SEND_FROM_INDEX]);
+            assert_eq!(send_accounts.asset_fee().key, &keys[ASSET_FEE_INDEX]);
+            assert_eq!(send_accounts.debridge_program().key, &keys[17]);
+        });
+    }
+
+    #[test]
+    fn test_check_send_addresses_len() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[CHAIN_SUPPORT_INFO_INDEX] = Pubkey::find_chain_support_info_address(&POLYGON_CHAIN_ID)
+            .unwrap()
+            .0;
+        let mut data = vec![vec![]; 18];
+        data[CHAIN_SUPPORT_INFO_INDEX] = chain_support_info_data(ChainSupportInfo::Supported {
+            fixed_fee: None,
+            transfer_fee_bps: None,
+            chain_address_len: 20,
+        });
+
+        with_account_infos(&keys, data, |account_infos| {
+            let mut send_ix = SendIx {
+                target_chain_id: POLYGON_CHAIN_ID,
+                receiver: vec![1; 20],
+                is_use_asset_fee: false,
+                amount: 1000,
+                submission_params: Some(SendSubmissionParamsInput::execution_fee_only(
+                    100,
+                    get_chain_address_len(account_infos, POLYGON_CHAIN_ID).unwrap(),
+                )),
+                referral_code: None,
+            };
+            assert_eq!(check_send_addresses_len(account_infos, &send_ix), Ok(()));
+
+            send_ix.submission_params =
+                Some(SendSubmissionParamsInput::execution_fee_only(100, 32));
+            assert_eq!(
+                check_send_addresses_len(account_infos, &send_ix),
+                Err(Error::WrongFallbackAddressLength)
+            );
+
+            send_ix.receiver = vec![1; 32];
+            assert_eq!(
+                check_send_addresses_len(account_infos, &send_ix),
+                Err(Error::WrongReceiverAddressLength)
+            );
+        });
+    }
+
+    fn bridge_data(max_amount: u64, state: u8) -> Vec<u8> {
+        let mut bridge_data = vec![231, 232, 31, 98, 110, 3, 23, 59];
+        bridge_data.extend(max_amount.to_le_bytes());
+        bridge_data.extend([0; 24]);
+        bridge_data.push(state);
+        bridge_data.extend([0; 24]);
+        bridge_data
+    }
+
+    #[test]
+    fn test_preflight_send() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[CHAIN_SUPPORT_INFO_INDEX] = Pubkey::find_chain_support_info_address(&POLYGON_CHAIN_ID)
+            .unwrap()
+            .0;
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: false,
+            amount: 1000,
+            submission_params: None,
+            referral_code: None,
+        };
+        let preflight = |state_status: u8, bridge_state: u8, max_amount: u64, chain_supported| {
+            let mut data = vec![vec![]; 18];
+            data[BRIDGE_INDEX] = bridge_data(max_amount, bridge_state);
+            data[STATE_INDEX] = state_data(&Pubkey::new_unique(), 10000, 10);
+            data[STATE_INDEX][8] = state_status;
+            data[CHAIN_SUPPORT_INFO_INDEX] = chain_support_info_data(match chain_supported {
+                true => ChainSupportInfo::Supported {
+                    fixed_fee: None,
+                    transfer_fee_bps: None,
+                    chain_address_len: 20,
+                },
+                false => ChainSupportInfo::NotSupported,
+            });
+
+            with_account_infos(&keys, data, |account_infos| {
+                preflight_send(account_infos, &send_ix)
+            })
+        };
+
+        assert_eq!(preflight(0, 0, 1000, true), Ok(()));
+        assert_eq!(preflight(1, 0, 1000, true), Err(Error::ProtocolPaused));
+        assert_eq!(preflight(0, 1, 1000, true), Err(Error::BridgePaused));
+        assert_eq!(
+            preflight(0, 0, 1000, false),
+            Err(Error::TargetChainNotSupported)
+        );
+        assert_eq!(
+            preflight(0, 0, 999, true),
+            Err(Error::AmountExceedsBridgeMax)
+        );
+    }
+
+    pub(super) fn fee_accounts() -> (Vec<Pubkey>, Vec<Vec<u8>>) {
+        let token_mint = Pubkey::new_unique();
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[TOKEN_MINT_INDEX] = token_mint;
+        keys[CHAIN_SUPPORT_INFO_INDEX] = Pubkey::find_chain_support_info_address(&POLYGON_CHAIN_ID)
+            .unwrap()
+            .0;
+        keys[DISCOUNT_INDEX] = Pubkey::find_no_discount_address().0;
+        keys[ASSET_FEE_INDEX] = Pubkey::find_asset_fee_info_address(
+            &Pubkey::find_bridge_address(&token_mint).0,
+            &POLYGON_CHAIN_ID,
+        )
+        .unwrap()
+        .0;
+        let mut data = vec![vec![]; 18];
+        data[TOKEN_MINT_INDEX] = vec![0; 82];
+        data[STATE_INDEX] = state_data(&Pubkey::new_unique(), 10000, 10);
+        data[CHAIN_SUPPORT_INFO_INDEX] = chain_support_info_data(ChainSupportInfo::Supported {
+            fixed_fee: None,
+            transfer_fee_bps: None,
+            chain_address_len: 20,
+        });
+        data[ASSET_FEE_INDEX] = vec![
+            37, 184, 34, 110, 54, 84, 57, 85, 255, 1, 100, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        (keys, data)
+    }
+
+    #[test]
+    fn test_discount() {
+        let (mut keys, mut data) = fee_accounts();
+
+        with_account_infos(&keys, data.clone(), |account_infos| {
+            assert_eq!(get_discount(account_infos), Ok(DiscountInfo::default()));
+            assert_eq!(get_transfer_fee(account_infos, POLYGON_CHAIN_ID), Ok(10));
+            assert_eq!(
+                get_chain_native_fix_fee(account_infos, POLYGON_CHAIN_ID),
+                Ok(10000)
+            );
+            assert_eq!(
+                try_get_chain_asset_fix_fee(account_infos, POLYGON_CHAIN_ID),
+                Ok(100)
+            );
+        });
+
+        keys[DISCOUNT_INDEX] = Pubkey::find_discount_info_address(&keys[SEND_FROM_INDEX]).0;
+        let discount = DiscountInfo {
+            fix_bps: 2500,
+            transfer_bps: 5000,
+        };
+        data[DISCOUNT_INDEX] = [
+            vec![65, 172, 32, 7, 173, 203, 143, 107],
+            discount.try_to_vec().unwrap(),
+        ]
+        .concat();
+
+        with_account_infos(&keys, data.clone(), |account_infos| {
+            assert_eq!(get_discount(account_infos), Ok(discount));
+            assert_eq!(get_transfer_fee(account_infos, POLYGON_CHAIN_ID), Ok(5));
+            assert_eq!(
+                get_chain_native_fix_fee(account_infos, POLYGON_CHAIN_ID),
+                Ok(7500)
+            );
+            assert_eq!(
+                try_get_chain_asset_fix_fee(account_infos, POLYGON_CHAIN_ID),
+                Ok(75)
+            );
+            assert_eq!(
+                add_all_fees(account_infos, POLYGON_CHAIN_ID, 1000, 100, true),
+                Ok(1175)
+            );
+
+            let quote = quote_send(
+                account_infos,
+                POLYGON_CHAIN_ID,
+                QuoteAmount::ExactAmount(1000),
+                100,
+                false,
+                0,
+            )
+            .unwrap();
+            assert_eq!(quote.native_fix_fee, 7500);
+            assert_eq!(quote.amount, 1100 * 10000 / 9995);
+        });
+
+        let mut over_discount_data = data.clone();
+        over_discount_data[DISCOUNT_INDEX] = [
+            vec![65, 172, 32, 7, 173, 203, 143, 107],
+            DiscountInfo {
+                fix_bps: 10001,
+                transfer_bps: 10000,
+            }
+            .try_to_vec()
+            .unwrap(),
+        ]
+        .concat();
+        with_account_infos(&keys, over_discount_data, |account_infos| {
+            assert_eq!(get_transfer_fee(account_infos, POLYGON_CHAIN_ID), Ok(0));
+            assert_eq!(
+                get_chain_native_fix_fee(account_infos, POLYGON_CHAIN_ID),
+                Err(Error::DiscountBpsExceedsDenominator)
+            );
+        });
+
+        keys[DISCOUNT_INDEX] = Pubkey::new_unique();
+        with_account_infos(&keys, data, |account_infos| {
+            assert_eq!(get_discount(account_infos), Err(Error::WrongDiscount));
+            assert_eq!(
+                get_transfer_fee(account_infos, POLYGON_CHAIN_ID),
+                Err(Error::WrongDiscount)
+            );
+            assert_eq!(
+                add_all_fees(account_infos, POLYGON_CHAIN_ID, 1000, 100, true),
+                Err(Error::WrongDiscount)
+            );
+        });
+    }
+
+    #[test]
+    fn test_check_fee_limits() {
+        let (keys, data) = fee_accounts();
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: false,
+            amount: 10000,
+            submission_params: Some(SendSubmissionParamsInput::execution_fee_only(100, 20)),
+            referral_code: None,
+        };
+        let with_asset_fee = SendIx {
+            is_use_asset_fee: true,
+            ..send_ix.clone()
+        };
+        let fee_limits = FeeLimits {
+            max_native_fix_fee: 10000,
+            max_transfer_fee_bps: 10,
+            max_asset_fix_fee: 100,
+            min_received_amount: 9890,
+        };
+
+        with_account_infos(&keys, data, |account_infos| {
+            assert_eq!(
+                check_fee_limits(account_infos, &send_ix, &FeeLimits::default()),
+                Ok(())
+            );
+            assert_eq!(
+                check_fee_limits(account_infos, &send_ix, &fee_limits),
+                Ok(())
+            );
+            assert_eq!(
+                check_fee_limits(
+                    account_infos,
+                    &send_ix,
+                    &FeeLimits {
+                        max_native_fix_fee: 9999,
+                        ..fee_limits.clone()
+                    }
+                ),
+                Err(Error::NativeFixFeeExceedsLimit)
+            );
+            assert_eq!(
+                check_fee_limits(
+                    account_infos,
+                    &send_ix,
+                    &FeeLimits {
+                        max_transfer_fee_bps: 9,
+                        ..fee_limits.clone()
+                    }
+                ),
+                Err(Error::TransferFeeExceedsLimit)
+            );
+            assert_eq!(
+                check_fee_limits(
+                    account_infos,
+                    &send_ix,
+                    &FeeLimits {
+                        min_received_amount: 9891,
+                        ..fee_limits.clone()
+                    }
+                ),
+                Err(Error::ReceivedAmountBelowMinimum)
+            );
+            assert_eq!(
+                check_fee_limits(
+                    account_infos,
+                    &with_asset_fee,
+                    &FeeLimits {
+                        max_native_fix_fee: 0,
+                        min_received_amount: 9790,
+                        ..fee_limits.clone()
+                    }
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                check_fee_limits(
+                    account_infos,
+                    &with_asset_fee,
+                    &FeeLimits {
+                        max_asset_fix_fee: 99,
+                        ..fee_limits.clone()
+                    }
+                ),
+                Err(Error::AssetFixFeeExceedsLimit)
+            );
+            assert_eq!(
+                check_fee_limits(
+                    account_infos,
+                    &SendIx {
+                        amount: 50,
+                        ..with_asset_fee.clone()
+                    },
+                    &FeeLimits::default()
+                ),
+                Err(Error::ReceivedAmountBelowMinimum)
+            );
+        });
+    }
+
+    #[test]
+    fn test_quote_send() {
+        let (keys, data) = fee_accounts();
+
+        with_account_infos(&keys, data, |account_infos| {
+            let quote = |quote_amount, is_use_asset_fee| {
+                quote_send(
+                    account_infos,
+                    POLYGON_CHAIN_ID,
+                    quote_amount,
+                    100,
+                    is_use_asset_fee,
+                    0,
+                )
+            };
+
+            let native_fee_quote = FeeQuote {
+                native_fix_fee: 10000,
+                asset_fix_fee: 0,
+                transfer_fee: 1,
+                token_transfer_fee: 0,
+                execution_fee: 100,
+                external_call_rent: 0,
+                amount: 1101,
+                received_amount: 1000,
+            };
+            assert_eq!(
+                quote(QuoteAmount::ExactAmount(1000), false),
+                Ok(native_fee_quote.clone())
+            );
+            assert_eq!(
+                quote(QuoteAmount::Amount(1101), false),
+                Ok(native_fee_quote)
+            );
+            assert_eq!(
+                add_all_fees(account_infos, POLYGON_CHAIN_ID, 1000, 100, false),
+                Ok(1101)
+            );
+            assert_eq!(
+                subtract_all_fees(account_infos, POLYGON_CHAIN_ID, 1101, 100, false),
+                Ok(1000)
+            );
+            assert_eq!(
+                received_amount_for(account_infos, POLYGON_CHAIN_ID, 1201, true),
+                Ok(1100)
+            );
+
+            let asset_fee_quote = FeeQuote {
+                native_fix_fee: 0,
+                asset_fix_fee: 100,
+                transfer_fee: 1,
+                token_transfer_fee: 0,
+                execution_fee: 100,
+                external_call_rent: 0,
+                amount: 1201,
+                received_amount: 1000,
+            };
+            assert_eq!(
+                quote(QuoteAmount::ExactAmount(1000), true),
+                Ok(asset_fee_quote.clone())
+            );
+            assert_eq!(quote(QuoteAmount::Amount(1201), true), Ok(asset_fee_quote));
+
+            assert_eq!(
+                quote(QuoteAmount::Amount(150), true),
+                Err(Error::AmountTooSmallToCoverFees)
+            );
+        });
+    }
+
+    #[test]
+    fn test_token_2022_transfer_fee() {
+        let (keys, mut data) = fee_accounts();
+
+        let transfer_fee = [
+            0_u64.to_le_bytes().as_slice(),
+            &5_u64.to_le_bytes(),
+            &100_u16.to_le_bytes(),
+        ]
+        .concat();
+        let mut mint_data = vec![0; 165];
+        mint_data.push(1);
+        mint_data.extend(1_u16.to_le_bytes());
+        mint_data.extend(108_u16.to_le_bytes());
+        mint_data.extend([0; 72]);
+        mint_data.extend(&transfer_fee);
+        mint_data.extend(&transfer_fee);
+        data[TOKEN_MINT_INDEX] = mint_data;
+
+        with_owned_account_infos(&keys, data, &SPL_TOKEN_2022_ID, |account_infos| {
+            assert_eq!(
+                add_all_fees(account_infos, POLYGON_CHAIN_ID, 1000, 100, false),
+                Ok(1106)
+            );
+            assert_eq!(
+                subtract_all_fees(account_infos, POLYGON_CHAIN_ID, 1106, 100, false),
+                Ok(1000)
+            );
+
+            let fee_quote = FeeQuote {
+                native_fix_fee: 10000,
+                asset_fix_fee: 0,
+                transfer_fee: 1,
+                token_transfer_fee: 5,
+                execution_fee: 100,
+                external_call_rent: 0,
+                amount: 1106,
+                received_amount: 1000,
+            };
+            for quote_amount in [QuoteAmount::ExactAmount(1000), QuoteAmount::Amount(1106)] {
+                assert_eq!(
+                    quote_send(account_infos, POLYGON_CHAIN_ID, quote_amount, 100, false, 0),
+                    Ok(fee_quote.clone())
+                );
+            }
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn test_subtract_transfer_fee_is_inverse_of_add(
+            exact_amount in 0..=u64::MAX / BPS_DENOMINATOR,
+            transfer_fee_bps in 0..BPS_DENOMINATOR,
+        ) {
+            let amount = add_transfer_fee_bps(exact_amount, transfer_fee_bps).unwrap();
+            let received_amount = subtract_transfer_fee_bps(amount, transfer_fee_bps).unwrap();
+
+            prop_assert!(received_amount >= exact_amount);
+            // Floor division makes `amount` the largest amount that is received as `exact_amount`
+            prop_assert_eq!(received_amount, exact_amount);
+            if let Some(amount) = amount.checked_add(1) {
+                prop_assert!(subtract_transfer_fee_bps(amount, transfer_fee_bps).unwrap() > exact_amount);
+            }
+        }
+
+        #[test]
+        fn test_add_transfer_fee_covers_subtracted(
+            amount in 0..=u64::MAX / BPS_DENOMINATOR,
+            transfer_fee_bps in 0..BPS_DENOMINATOR,
+        ) {
+            let received_amount = subtract_transfer_fee_bps(amount, transfer_fee_bps).unwrap();
+
+            prop_assert!(add_transfer_fee_bps(received_amount, transfer_fee_bps).unwrap() >= amount);
+        }
+    }
+
+    fn validate_with_keys(
+        keys: &[Pubkey],
+        token_mint: &Pubkey,
+        send_from: &Pubkey,
+        fee_beneficiary: &Pubkey,
+    ) -> Result<(), Error> {
+        let mut data = vec![vec![]; 18];
+        data[STATE_INDEX] = state_data(fee_beneficiary, 10000, 10);
+
+        with_owned_account_infos(keys, data, &keys[TOKEN_PROGRAM_INDEX], |account_infos| {
+            validate_send_accounts(
+                account_infos,
+                token_mint,
+                POLYGON_CHAIN_ID,
+                send_from,
+                SolanaKeccak256::hash(&[]),
+            )
+        })
+    }
+
+    #[test]
+    fn test_validate_send_accounts() {
+        let token_mint = Pubkey::new_unique();
+        let send_from = Pubkey::new_unique();
+        let fee_beneficiary = Pubkey::new_unique();
+        let bridge = Pubkey::find_bridge_address(&token_mint).0;
+        let mint_authority = Pubkey::find_mint_authority_address(&bridge).0;
+        let external_call_storage =
+            Pubkey::find_external_call_storage_address(&SolanaKeccak256::hash(&[]), &send_from).0;
+
+        let keys = vec![
+            bridge,
+            token_mint,
+            Pubkey::find_associated_token_address(&mint_authority, &token_mint).0,
+            mint_authority,
+            Pubkey::find_chain_support_info_address(&POLYGON_CHAIN_ID)
+                .unwrap()
+                .0,
+            SETTINGS_ID,
+            SPL_TOKEN_ID,
+            Pubkey::find_state_address().0,
+            fee_beneficiary,
+            Pubkey::find_nonce_storage_address().0,
+            Pubkey::find_associated_token_address(&send_from, &token_mint).0,
+            system_program::ID,
+            external_call_storage,
+            Pubkey::find_external_call_meta_address(&external_call_storage).0,
+            send_from,
+            Pubkey::find_no_discount_address().0,
+            Pubkey::default_bridge_fee_address().unwrap().0,
+            DEBRIDGE_ID,
+        ];
+
+        assert_eq!(
+            validate_with_keys(&keys, &token_mint, &send_from, &fee_beneficiary),
+            Ok(())
+        );
+
+        let mut wrong_keys = keys.clone();
+        wrong_keys[FEE_BENEFICIARY_INDEX] = Pubkey::new_unique();
+        assert_eq!(
+            validate_with_keys(&wrong_keys, &token_mint, &send_from, &fee_beneficiary),
+            Err(Error::WrongFeeBeneficiary)
+        );
+
+        let mut wrong_keys = keys.clone();
+        wrong_keys[EXTERNAL_CALL_STORAGE_INDEX] = Pubkey::new_unique();
+        assert_eq!(
+            validate_with_keys(&wrong_keys, &token_mint, &send_from, &fee_beneficiary),
+            Err(Error::WrongExternalCallStorage)
+        );
+
+        assert_eq!(
+            validate_with_keys(&keys, &token_mint, &Pubkey::new_unique(), &fee_beneficiary),
+            Err(Error::WrongSendFromWallet)
+        );
+
+        let bumps = SendBumps::find(
+            &token_mint,
+            &SPL_TOKEN_ID,
+            POLYGON_CHAIN_ID,
+            &send_from,
+            SolanaKeccak256::hash(&[]),
+        )
+        .unwrap();
+        let mut data = vec![vec![]; 18];
+        data[STATE_INDEX] = state_data(&fee_beneficiary, 10000, 10);
+        with_owned_account_infos(&keys, data, &SPL_TOKEN_ID, |account_infos| {
+            let validate = |bumps: SendBumps| {
+                SendAccounts::new(account_infos)?
+                    .with_bumps(bumps)
+                    .validate(
+                        &token_mint,
+                        POLYGON_CHAIN_ID,
+                        &send_from,
+                        SolanaKeccak256::hash(&[]),
+                    )
+            };
+
+            assert_eq!(validate(bumps), Ok(()));
+            assert_eq!(
+                validate(SendBumps {
+                    external_call_storage: bumps.external_call_storage.map(|bump| bump ^ 1),
+                    ..bumps
+                }),
+                Err(Error::WrongExternalCallStorage)
+            );
+        });
+    }
+
+    #[test]
+    fn test_fee_helpers_with_bumps() {
+        let (keys, data) = fee_accounts();
+        let bumps = SendBumps::find(
+            &keys[TOKEN_MINT_INDEX],
+            &SPL_TOKEN_ID,
+            POLYGON_CHAIN_ID,
+            &keys[SEND_FROM_INDEX],
+            SolanaKeccak256::hash(&[]),
+        )
+        .unwrap();
+
+        with_account_infos(&keys, data, |account_infos| {
+            let send_accounts = SendAccounts::new(account_infos).unwrap().with_bumps(bumps);
+            assert_eq!(
+                send_accounts.get_transfer_fee(POLYGON_CHAIN_ID),
+                get_transfer_fee(account_infos, POLYGON_CHAIN_ID)
+            );
+            assert_eq!(
+                send_accounts.add_all_fees(POLYGON_CHAIN_ID, 1000, 100, true),
+                add_all_fees(account_infos, POLYGON_CHAIN_ID, 1000, 100, true)
+            );
+            assert_eq!(
+                send_accounts.try_get_chain_asset_fix_fee(POLYGON_CHAIN_ID),
+                Ok(100)
+            );
+            assert_eq!(
+                send_accounts
+                    .with_bumps(SendBumps {
+                        chain_support_info: bumps.chain_support_info.map(|bump| bump ^ 1),
+                        ..bumps
+                    })
+                    .get_transfer_fee(POLYGON_CHAIN_ID),
+                Err(Error::WrongChainSupportInfo)
+            );
+        });
+    }
+
+    /// Take the highest bump below canonical one, so the address differs from the found one
+    fn non_canonical(canonical: u8, create: impl Fn(u8) -> Option<Pubkey>) -> (Pubkey, u8) {
+        (0..canonical)
+            .rev()
+            .find_map(|bump| create(bump).map(|address| (address, bump)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_send_paths_with_bumps() {
+        let (mut keys, mut data) = fee_accounts();
+        keys[DEBRIDGE_PROGRAM_INDEX] = DEBRIDGE_ID;
+        let external_call = vec![1, 2, 3];
+        keys[EXTERNAL_CALL_STORAGE_INDEX] = Pubkey::find_external_call_storage_address(
+            &SolanaKeccak256::hash(&external_call),
+            &keys[SEND_FROM_INDEX],
+        )
+        .0;
+        keys[EXTERNAL_CALL_META_INDEX] =
+            Pubkey::find_external_call_meta_address(&keys[EXTERNAL_CALL_STORAGE_INDEX]).0;
+        data[NONCE_STORAGE_INDEX] = [
+            [116, 193, 37, 78, 49, 76, 253, 53].as_slice(),
+            &5_u64.to_le_bytes(),
+        ]
+        .concat();
+        data[DISCOUNT_INDEX] = [
+            vec![65, 172, 32, 7, 173, 203, 143, 107],
+            DiscountInfo::default().try_to_vec().unwrap(),
+        ]
+        .concat();
+        data[SEND_FROM_WALLET_INDEX] = vec![0; 165];
+
+        // Accounts are created with non-canonical bumps, so any `find_*` lookup
+        // returns another address and fails the send
+        let (bridge, bridge_bump) = Pubkey::find_bridge_address(&keys[TOKEN_MINT_INDEX]);
+        let chain_support_info_bump;
+        (keys[CHAIN_SUPPORT_INFO_INDEX], chain_support_info_bump) = non_canonical(
+            Pubkey::find_chain_support_info_address(&POLYGON_CHAIN_ID)
+                .unwrap()
+                .1,
+            |bump| Pubkey::create_chain_support_info_address(&POLYGON_CHAIN_ID, bump).unwrap(),
+        );
+        let discount_bump;
+        (keys[DISCOUNT_INDEX], discount_bump) = non_canonical(
+            Pubkey::find_discount_info_address(&keys[SEND_FROM_INDEX]).1,
+            |bump| Pubkey::create_discount_info_address(&keys[SEND_FROM_INDEX], bump).unwrap(),
+        );
+        let asset_fee_bump;
+        (keys[ASSET_FEE_INDEX], asset_fee_bump) = non_canonical(
+            Pubkey::find_asset_fee_info_address(&bridge, &POLYGON_CHAIN_ID)
+                .unwrap()
+                .1,
+            |bump| Pubkey::create_asset_fee_info_address(&bridge, &POLYGON_CHAIN_ID, bump).unwrap(),
+        );
+        let bumps = SendBumps {
+            bridge: Some(bridge_bump),
+            chain_support_info: Some(chain_support_info_bump),
+            discount: Some(discount_bump),
+            asset_fee: Some(asset_fee_bump),
+            ..SendBumps::default()
+        };
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: true,
+            amount: 1000,
+            submission_params: None,
+            referral_code: None,
+        };
+
+        with_account_infos(&keys, data, |account_infos| {
+            let send_accounts = SendAccounts::new(account_infos).unwrap();
+            let bumped = send_accounts.with_bumps(bumps);
+
+            assert_eq!(
+                count_invokes(|| bumped
+                    .invoke_debridge_send_with_fee_limits(send_ix.clone(), &FeeLimits::default())
+                    .map(|receipt| receipt.nonce)),
+                (Ok(5), 1)
+            );
+            assert_eq!(
+                count_invokes(|| bumped
+                    .invoke_debridge_send_checked(
+                        SendIx {
+                            is_use_asset_fee: false,
+                            amount: 0,
+                            ..send_ix.clone()
+                        },
+                        &FeeQuote::default()
+                    )
+                    .map(|receipt| receipt.nonce)),
+                (Ok(5), 1)
+            );
+            assert_eq!(
+                count_invokes(|| bumped
+                    .invoke_send_message_with_fee_limits(
+                        external_call.clone(),
+                        POLYGON_CHAIN_ID,
+                        vec![1; 20],
+                        100,
+                        vec![2; 20],
+                        None,
+                        &FeeLimits::default(),
+                    )
+                    .map_err(ProgramError::from)),
+                (Ok(()), 2)
+            );
+            assert_eq!(
+                count_invokes(|| bumped
+                    .invoke_send_with_external_call_with_fee_limits(
+                        external_call.clone(),
+                        POLYGON_CHAIN_ID,
+                        vec![1; 20],
+                        1000,
+                        100,
+                        vec![2; 20],
+                        [0; 32],
+                        None,
+                        &FeeLimits::default(),
+                    )
+                    .map(|receipt| receipt.nonce)
+                    .map_err(ProgramError::from)),
+                (Ok(5), 2)
+            );
+            assert_eq!(
+                count_invokes(|| bumped
+                    .invoke_send_hashed_message(
+                        SolanaKeccak256::hash(&external_call),
+                        POLYGON_CHAIN_ID,
+                        vec![1; 20],
+                        100,
+                        vec![2; 20],
+                        None,
+                    )
+                    .map(|receipt| receipt.nonce)
+                    .map_err(ProgramError::from)),
+                (Ok(5), 1)
+            );
+
+            assert_eq!(
+                send_accounts
+                    .invoke_debridge_send_with_fee_limits(send_ix.clone(), &FeeLimits::default())
+                    .map(|receipt| receipt.nonce),
+                Err(Error::WrongDiscount.into())
+            );
+            assert_eq!(
+                invoke_send_hashed_message(
+                    SolanaKeccak256::hash(&external_call),
+                    POLYGON_CHAIN_ID,
+                    vec![1; 20],
+                    100,
+                    vec![2; 20],
+                    None,
+                    account_infos,
+                )
+                .map(|receipt| receipt.nonce)
+                .map_err(ProgramError::from),
+                Err(Error::WrongDiscount.into())
+            );
+        });
+    }
+}
\ No newline at end of file
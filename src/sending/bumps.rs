@@ -0,0 +1,227 @@
+/*
+ * Copyright (C) 2023 debridge
+ *
+ * This file is part of debridge-solana-sdk.
+ *
+ * debridge-solana-sdk is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Lesser General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * debridge-solana-sdk is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with debridge-solana-sdk. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::{
+    keys::{
+        AssetFeeInfoPubkey, AssociatedTokenPubkey, BridgePubkey, ChainSupportInfoPubkey,
+        DiscountInfoPubkey, ExternalCallMetaPubkey, ExternalCallStoragePubkey, MintAuthorityPubkey,
+    },
+    sending::SendAccounts,
+    Error, Pubkey,
+};
+
+/// Bumps of PDA accounts of the send context stored by the program, e.g. in its state.
+/// With a bump the address is checked with one `create_program_address` instead of
+/// `find_program_address`. Addresses with `None` bump are found as usual
+///
+/// Pass them to [`SendAccounts::with_bumps`] to use them in lookups of the view.
+/// Get all bumps once with [`SendBumps::find`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SendBumps {
+    /// Bump of bridge of sending token mint
+    pub bridge: Option<u8>,
+    /// Bump of mint authority of bridge
+    pub mint_authority: Option<u8>,
+    /// Bump of associated token account of mint authority, the staking wallet
+    pub staking_wallet: Option<u8>,
+    /// Bump of chain support info of target chain
+    pub chain_support_info: Option<u8>,
+    /// Bump of associated token account of sender, the send-from wallet
+    pub send_from_wallet: Option<u8>,
+    /// Bump of external call storage of sender for external call shortcut
+    pub external_call_storage: Option<u8>,
+    /// Bump of external call meta of external call storage
+    pub external_call_meta: Option<u8>,
+    /// Bump of discount info of sender. Not needed if sender has no discount
+    pub discount: Option<u8>,
+    /// Bump of asset fee info of bridge. Not needed if default bridge fee is used
+    pub asset_fee: Option<u8>,
+}
+
+impl SendBumps {
+    /// Find bumps of all PDA accounts of the send context. Call it off-chain
+    /// or once on-chain and store the result
+    ///
+    /// # Arguments
+    /// * `token_mint` - mint of sending tokens
+    /// * `token_program` - program owning token mint, spl-token or Token-2022 program
+    /// * `target_chain_id` - chain id to which the tokens are sent
+    /// * `send_from` - sender of tokens, owner of the send-from wallet and external call storage
+    /// * `external_call_shortcut` - keccak256 hash of external call buffer
+    pub fn find(
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+        target_chain_id: [u8; 32],
+        send_from: &Pubkey,
+        external_call_shortcut: [u8; 32],
+    ) -> Result<Self, Error> {
+        let (bridge, bridge_bump) = Pubkey::find_bridge_address(token_mint);
+        let (mint_authority, mint_authority_bump) = Pubkey::find_mint_authority_address(&bridge);
+        let (external_call_storage, external_call_storage_bump) =
+            Pubkey::find_external_call_storage_address(&external_call_shortcut, send_from);
+
+        Ok(SendBumps {
+            bridge: Some(bridge_bump),
+            mint_authority: Some(mint_authority_bump),
+            staking_wallet: Some(
+                Pubkey::find_associated_token_address_with_program_id(
+                    &mint_authority,
+                    token_mint,
+                    token_program,
+                )
+                .1,
+            ),
+            chain_support_info: Some(Pubkey::find_chain_support_info_address(&target_chain_id)?.1),
+            send_from_wallet: Some(
+                Pubkey::find_associated_token_address_with_program_id(
+                    send_from,
+                    token_mint,
+                    token_program,
+                )
+                .1,
+            ),
+            external_call_storage: Some(external_call_storage_bump),
+            external_call_meta: Some(
+                Pubkey::find_external_call_meta_address(&external_call_storage).1,
+            ),
+            discount: Some(Pubkey::find_discount_info_address(send_from).1),
+            asset_fee: Some(Pubkey::find_asset_fee_info_address(&bridge, &target_chain_id)?.1),
+        })
+    }
+
+    pub(super) fn bridge_address(&self, token_mint: &Pubkey) -> Result<Pubkey, Error> {
+        match self.bridge {
+            Some(bump) => {
+                Pubkey::create_bridge_address(token_mint, bump)?.ok_or(Error::WrongBridge)
+            }
+            None => Ok(Pubkey::find_bridge_address(token_mint).0),
+        }
+    }
+
+    pub(super) fn mint_authority_address(&self, bridge: &Pubkey) -> Result<Pubkey, Error> {
+        match self.mint_authority {
+            Some(bump) => Pubkey::create_mint_authority_address(bridge, bump)?
+                .ok_or(Error::WrongMintAuthority),
+            None => Ok(Pubkey::find_mint_authority_address(bridge).0),
+        }
+    }
+
+    pub(super) fn associated_token_address(
+        bump: Option<u8>,
+        owner: &Pubkey,
+        token_mint: &Pubkey,
+        token_program: &Pubkey,
+        err: Error,
+    ) -> Result<Pubkey, Error> {
+        match bump {
+            Some(bump) => Pubkey::create_associated_token_address_with_program_id(
+                owner,
+                token_mint,
+                token_program,
+                bump,
+            )?
+            .ok_or(err),
+            None => Ok(Pubkey::find_associated_token_address_with_program_id(
+                owner,
+                token_mint,
+                token_program,
+            )
+            .0),
+        }
+    }
+
+    pub(super) fn chain_support_info_address(
+        &self,
+        target_chain_id: &[u8; 32],
+    ) -> Result<Pubkey, Error> {
+        match self.chain_support_info {
+            Some(bump) => Pubkey::create_chain_support_info_address(target_chain_id, bump)
+                .map_err(|_| Error::WrongChainSupportInfo)?
+                .ok_or(Error::WrongChainSupportInfo),
+            None => Ok(Pubkey::find_chain_support_info_address(target_chain_id)?.0),
+        }
+    }
+
+    pub(super) fn external_call_storage_address(
+        &self,
+        external_call_shortcut: &[u8; 32],
+        send_from: &Pubkey,
+    ) -> Result<Pubkey, Error> {
+        match self.external_call_storage {
+            Some(bump) => Pubkey::create_external_call_storage_address(
+                external_call_shortcut,
+                send_from,
+                bump,
+            )?
+            .ok_or(Error::WrongExternalCallStorage),
+            None => {
+                Ok(Pubkey::find_external_call_storage_address(external_call_shortcut, send_from).0)
+            }
+        }
+    }
+
+    pub(super) fn external_call_meta_address(
+        &self,
+        external_call_storage: &Pubkey,
+    ) -> Result<Pubkey, Error> {
+        match self.external_call_meta {
+            Some(bump) => {
+                Pubkey::create_external_call_meta_address(&external_call_storage.to_bytes(), bump)?
+                    .ok_or(Error::WrongExternalCallMeta)
+            }
+            None => Ok(Pubkey::find_external_call_meta_address(external_call_storage).0),
+        }
+    }
+
+    pub(super) fn discount_info_address(&self, send_from: &Pubkey) -> Result<Pubkey, Error> {
+        match self.discount {
+            Some(bump) => {
+                Pubkey::create_discount_info_address(send_from, bump)?.ok_or(Error::WrongDiscount)
+            }
+            None => Ok(Pubkey::find_discount_info_address(send_from).0),
+        }
+    }
+
+    pub(super) fn asset_fee_info_address(
+        &self,
+        bridge: &Pubkey,
+        target_chain_id: &[u8; 32],
+    ) -> Result<Pubkey, Error> {
+        match self.asset_fee {
+            Some(bump) => Pubkey::create_asset_fee_info_address(bridge, target_chain_id, bump)?
+                .ok_or(Error::WrongBridgeFeeInfo),
+            None => Ok(Pubkey::find_asset_fee_info_address(bridge, target_chain_id)?.0),
+        }
+    }
+}
+
+impl<'a, 'info> SendAccounts<'a, 'info> {
+    /// Use stored bumps of PDA accounts in lookups of this view
+    ///
+    /// # Arguments
+    /// * `bumps` - stored bumps of PDA accounts of the send context
+    pub fn with_bumps(self, bumps: SendBumps) -> Self {
+        SendAccounts { bumps, ..self }
+    }
+
+    /// Stored bumps used in lookups of this view
+    pub fn bumps(&self) -> &SendBumps {
+        &self.bumps
+    }
+}
@@ -0,0 +1,472 @@
+/*
+ * Copyright (C) 2023 debridge
+ *
+ * This file is part of debridge-solana-sdk.
+ *
+ * debridge-solana-sdk is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Lesser General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * debridge-solana-sdk is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with debridge-solana-sdk. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    system_instruction,
+};
+
+use crate::{
+    errors::InvokeError,
+    sending::{
+        add_all_fees, check_fee_limits, invoke_debridge_send_with_receipt_signed, FeeLimits,
+        SendAccounts, SendIx, SendReceipt,
+    },
+    token, Error, Pubkey, SPL_TOKEN_ID,
+};
+
+/// Mint of wrapped SOL
+pub const NATIVE_MINT: Pubkey = Pubkey::new_from_array(env_to_array::bs58_to_array!(
+    "So11111111111111111111111111111111111111112"
+));
+
+const CLOSE_ACCOUNT_TAG: u8 = 9;
+const SYNC_NATIVE_TAG: u8 = 17;
+
+/// Send native SOL. Lamports of send_from are wrapped into its wSOL wallet
+/// before the send, so no separate wrapping transaction is needed
+///
+/// The wSOL wallet of send_from has to exist. Only lamports missing in the wallet
+/// are wrapped. `send_ix` amount is replaced with `exact_amount` with all fees added
+/// by [`add_all_fees`]. Returns [`SendReceipt`] of created submission
+/// and the amount debited from the wallet
+///
+/// # Arguments
+/// * `exact_amount` - amount that will be received in target chain
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `unwrap_leftovers` - close wSOL wallet after the send and return its lamports to send_from.
+///   Fails with [`Error::WrappedSolWalletNotEmpty`] if the wallet holds wSOL before the send,
+///   so wSOL of send_from isn't unwrapped
+pub fn invoke_debridge_send_native(
+    exact_amount: u64,
+    send_ix: SendIx,
+    account_infos: &[AccountInfo],
+    unwrap_leftovers: bool,
+) -> Result<(SendReceipt, u64), InvokeError> {
+    invoke_debridge_send_native_signed(exact_amount, send_ix, account_infos, unwrap_leftovers, &[])
+}
+
+/// Send native SOL with using PDA account as send_from.
+/// You can using this function for sending SOL on behalf of the program.
+///
+/// # Arguments
+/// * `exact_amount` - amount that will be received in target chain
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `unwrap_leftovers` - close wSOL wallet after the send, only if it's empty before the send
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_debridge_send_native_signed(
+    exact_amount: u64,
+    send_ix: SendIx,
+    account_infos: &[AccountInfo],
+    unwrap_leftovers: bool,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(SendReceipt, u64), InvokeError> {
+    send_native_signed(
+        exact_amount,
+        send_ix,
+        None,
+        account_infos,
+        unwrap_leftovers,
+        signers_seeds,
+    )
+}
+
+/// Send native SOL if live fees don't exceed `fee_limits`.
+/// Fees are checked with [`check_fee_limits`] before wrapping
+///
+/// # Arguments
+/// * `exact_amount` - amount that will be received in target chain
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_limits` - fee limits accepted by sender
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `unwrap_leftovers` - close wSOL wallet after the send, only if it's empty before the send
+pub fn invoke_debridge_send_native_with_fee_limits(
+    exact_amount: u64,
+    send_ix: SendIx,
+    fee_limits: &FeeLimits,
+    account_infos: &[AccountInfo],
+    unwrap_leftovers: bool,
+) -> Result<(SendReceipt, u64), InvokeError> {
+    invoke_debridge_send_native_with_fee_limits_signed(
+        exact_amount,
+        send_ix,
+        fee_limits,
+        account_infos,
+        unwrap_leftovers,
+        &[],
+    )
+}
+
+/// Send native SOL if live fees don't exceed `fee_limits` with using PDA account as send_from.
+/// You can using this function for sending SOL on behalf of the program.
+///
+/// # Arguments
+/// * `exact_amount` - amount that will be received in target chain
+/// * `send_ix` - [`SendIx`] structure to send debridge instruction creation
+/// * `fee_limits` - fee limits accepted by sender
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `unwrap_leftovers` - close wSOL wallet after the send, only if it's empty before the send
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn invoke_debridge_send_native_with_fee_limits_signed(
+    exact_amount: u64,
+    send_ix: SendIx,
+    fee_limits: &FeeLimits,
+    account_infos: &[AccountInfo],
+    unwrap_leftovers: bool,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(SendReceipt, u64), InvokeError> {
+    send_native_signed(
+        exact_amount,
+        send_ix,
+        Some(fee_limits),
+        account_infos,
+        unwrap_leftovers,
+        signers_seeds,
+    )
+}
+
+fn send_native_signed(
+    exact_amount: u64,
+    mut send_ix: SendIx,
+    fee_limits: Option<&FeeLimits>,
+    account_infos: &[AccountInfo],
+    unwrap_leftovers: bool,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(SendReceipt, u64), InvokeError> {
+    let execution_fee = send_ix
+        .submission_params
+        .as_ref()
+        .map(|params| params.execution_fee)
+        .unwrap_or(0);
+    send_ix.amount = add_all_fees(
+        account_infos,
+        send_ix.target_chain_id,
+        exact_amount,
+        execution_fee,
+        send_ix.is_use_asset_fee,
+    )?;
+    let amount = send_ix.amount;
+    if let Some(fee_limits) = fee_limits {
+        check_fee_limits(account_infos, &send_ix, fee_limits)?;
+    }
+
+    if unwrap_leftovers
+        && get_token_amount(SendAccounts::try_from(account_infos)?.send_from_wallet())? > 0
+    {
+        return Err(Error::WrappedSolWalletNotEmpty.into());
+    }
+
+    wrap_native_signed(amount, account_infos, signers_seeds)?;
+    let receipt = invoke_debridge_send_with_receipt_signed(send_ix, account_infos, signers_seeds)?;
+
+    if unwrap_leftovers {
+        unwrap_native_signed(account_infos, signers_seeds)?;
+    }
+
+    Ok((receipt, amount))
+}
+
+/// Wrap lamports of send_from into its wSOL wallet to have at least `amount` in it
+///
+/// # Arguments
+/// * `amount` - amount of wSOL needed in send-from wallet
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn wrap_native(amount: u64, account_infos: &[AccountInfo]) -> Result<(), InvokeError> {
+    wrap_native_signed(amount, account_infos, &[])
+}
+
+/// Wrap lamports of send_from into its wSOL wallet with using PDA account as send_from
+///
+/// # Arguments
+/// * `amount` - amount of wSOL needed in send-from wallet
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn wrap_native_signed(
+    amount: u64,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), InvokeError> {
+    let accounts = SendAccounts::try_from(account_infos)?;
+    if accounts.token_mint().key.ne(&NATIVE_MINT) {
+        return Err(Error::WrongTokenMint.into());
+    }
+    check_token_program(&accounts)?;
+
+    let missing_amount = amount.saturating_sub(get_token_amount(accounts.send_from_wallet())?);
+    if missing_amount == 0 {
+        return Ok(());
+    }
+
+    invoke_signed(
+        &system_instruction::transfer(
+            accounts.send_from().key,
+            accounts.send_from_wallet().key,
+            missing_amount,
+        ),
+        &[
+            accounts.send_from().clone(),
+            accounts.send_from_wallet().clone(),
+            accounts.system_program().clone(),
+        ],
+        signers_seeds,
+    )?;
+
+    invoke_signed(
+        &sync_native_instruction(accounts.send_from_wallet().key),
+        &[
+            accounts.send_from_wallet().clone(),
+            accounts.token_program().clone(),
+        ],
+        &[],
+    )?;
+
+    Ok(())
+}
+
+/// Close wSOL wallet of send_from and return all its lamports to send_from.
+/// All wSOL held by the wallet is unwrapped, not only leftovers of a send
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+pub fn unwrap_native(account_infos: &[AccountInfo]) -> Result<(), InvokeError> {
+    unwrap_native_signed(account_infos, &[])
+}
+
+/// Close wSOL wallet of send_from with using PDA account as send_from
+///
+/// # Arguments
+/// * `account_infos` - account forming by client from debridge-typescript-sdk
+/// * `signer_seeds` - parameter is a slice of `u8` slices where the inner slices represent the seeds used to derive PDA account
+pub fn unwrap_native_signed(
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), InvokeError> {
+    let accounts = SendAccounts::try_from(account_infos)?;
+    check_token_program(&accounts)?;
+
+    invoke_signed(
+        &close_account_instruction(accounts.send_from_wallet().key, accounts.send_from().key),
+        &[
+            accounts.send_from_wallet().clone(),
+            accounts.send_from().clone(),
+            accounts.token_program().clone(),
+        ],
+        signers_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Wrapped SOL is spl-token mint, sync native and close account instructions are built for it
+fn check_token_program(accounts: &SendAccounts) -> Result<(), Error> {
+    if accounts.token_program().key.ne(&SPL_TOKEN_ID) {
+        return Err(Error::WrongTokenProgramId);
+    }
+
+    Ok(())
+}
+
+fn get_token_amount(token_account: &AccountInfo) -> Result<u64, Error> {
+    token::get_token_account_amount(token_account).map_err(|_| Error::WrongSendFromWallet)
+}
+
+fn sync_native_instruction(wallet: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        SPL_TOKEN_ID,
+        &[SYNC_NATIVE_TAG],
+        vec![AccountMeta::new(*wallet, false)],
+    )
+}
+
+fn close_account_instruction(wallet: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        SPL_TOKEN_ID,
+        &[CLOSE_ACCOUNT_TAG],
+        vec![
+            AccountMeta::new(*wallet, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::{program_error::ProgramError, system_instruction, system_program};
+
+    use crate::{
+        sending::{
+            native::{
+                close_account_instruction, get_token_amount, invoke_debridge_send_native,
+                sync_native_instruction, unwrap_native, wrap_native, NATIVE_MINT,
+            },
+            tests::{fee_accounts, record_invokes, with_account_infos},
+            SendIx, SEND_FROM_INDEX, SEND_FROM_WALLET_INDEX, TOKEN_MINT_INDEX, TOKEN_PROGRAM_INDEX,
+        },
+        Error, Pubkey, POLYGON_CHAIN_ID, SPL_TOKEN_2022_ID, SPL_TOKEN_ID,
+    };
+
+    #[test]
+    fn test_token_instructions() {
+        let wallet = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let sync_native = sync_native_instruction(&wallet);
+        assert_eq!(sync_native.program_id, SPL_TOKEN_ID);
+        assert_eq!(sync_native.data, vec![17]);
+        assert_eq!(sync_native.accounts.len(), 1);
+        assert!(sync_native.accounts[0].is_writable);
+
+        let close_account = close_account_instruction(&wallet, &owner);
+        assert_eq!(close_account.data, vec![9]);
+        assert_eq!(
+            close_account
+                .accounts
+                .iter()
+                .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+                .collect::<Vec<_>>(),
+            vec![
+                (wallet, false, true),
+                (owner, false, true),
+                (owner, true, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_native() {
+        let mut keys = (0..18).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        keys[TOKEN_PROGRAM_INDEX] = SPL_TOKEN_ID;
+        let mut data = vec![vec![]; 18];
+        data[SEND_FROM_WALLET_INDEX] = vec![0; 165];
+        data[SEND_FROM_WALLET_INDEX][64..72].copy_from_slice(&500u64.to_le_bytes());
+
+        let wrap = |keys: &[Pubkey], amount| {
+            with_account_infos(keys, data.clone(), |account_infos| {
+                assert_eq!(
+                    get_token_amount(&account_infos[SEND_FROM_WALLET_INDEX]),
+                    Ok(500)
+                );
+                record_invokes(|| wrap_native(amount, account_infos).map_err(ProgramError::from))
+            })
+        };
+        let unwrap = |keys: &[Pubkey]| {
+            with_account_infos(keys, data.clone(), |account_infos| {
+                record_invokes(|| unwrap_native(account_infos).map_err(ProgramError::from))
+            })
+        };
+
+        assert_eq!(
+            wrap(&keys, 1000),
+            (Err(Error::WrongTokenMint.into()), vec![])
+        );
+
+        keys[TOKEN_MINT_INDEX] = NATIVE_MINT;
+        assert_eq!(wrap(&keys, 400), (Ok(()), vec![]));
+
+        let (result, invoked) = wrap(&keys, 1000);
+        assert_eq!(result, Ok(()));
+        assert_eq!(invoked.len(), 2);
+        assert_eq!(invoked[0].program_id, system_program::ID);
+        assert_eq!(
+            invoked[0]
+                .accounts
+                .iter()
+                .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+                .collect::<Vec<_>>(),
+            vec![
+                (keys[SEND_FROM_INDEX], true, true),
+                (keys[SEND_FROM_WALLET_INDEX], false, true)
+            ]
+        );
+        assert_eq!(
+            invoked[0].data,
+            system_instruction::transfer(
+                &keys[SEND_FROM_INDEX],
+                &keys[SEND_FROM_WALLET_INDEX],
+                500
+            )
+            .data
+        );
+        assert_eq!(invoked[0].data[4..], 500u64.to_le_bytes());
+        assert_eq!(invoked[1].program_id, SPL_TOKEN_ID);
+        assert_eq!(invoked[1].data, vec![17]);
+        assert_eq!(invoked[1].accounts[0].pubkey, keys[SEND_FROM_WALLET_INDEX]);
+
+        let (result, invoked) = unwrap(&keys);
+        assert_eq!(result, Ok(()));
+        assert_eq!(invoked.len(), 1);
+        assert_eq!(invoked[0].program_id, SPL_TOKEN_ID);
+        assert_eq!(invoked[0].data, vec![9]);
+        assert_eq!(
+            invoked[0]
+                .accounts
+                .iter()
+                .map(|meta| meta.pubkey)
+                .collect::<Vec<_>>(),
+            vec![
+                keys[SEND_FROM_WALLET_INDEX],
+                keys[SEND_FROM_INDEX],
+                keys[SEND_FROM_INDEX]
+            ]
+        );
+
+        keys[TOKEN_PROGRAM_INDEX] = SPL_TOKEN_2022_ID;
+        assert_eq!(
+            wrap(&keys, 1000),
+            (Err(Error::WrongTokenProgramId.into()), vec![])
+        );
+        assert_eq!(
+            unwrap(&keys),
+            (Err(Error::WrongTokenProgramId.into()), vec![])
+        );
+    }
+
+    #[test]
+    fn test_send_native_keeps_non_empty_wallet() {
+        let (mut keys, mut data) = fee_accounts();
+        keys[TOKEN_MINT_INDEX] = NATIVE_MINT;
+        keys[TOKEN_PROGRAM_INDEX] = SPL_TOKEN_ID;
+        data[SEND_FROM_WALLET_INDEX] = vec![0; 165];
+        data[SEND_FROM_WALLET_INDEX][64..72].copy_from_slice(&500u64.to_le_bytes());
+        let send_ix = SendIx {
+            target_chain_id: POLYGON_CHAIN_ID,
+            receiver: vec![1; 20],
+            is_use_asset_fee: false,
+            amount: 0,
+            submission_params: None,
+            referral_code: None,
+        };
+
+        with_account_infos(&keys, data, |account_infos| {
+            assert_eq!(
+                record_invokes(
+                    || invoke_debridge_send_native(1000, send_ix, account_infos, true)
+                        .map(|(_, amount)| amount)
+                        .map_err(ProgramError::from)
+                ),
+                (Err(Error::WrappedSolWalletNotEmpty.into()), vec![])
+            );
+        });
+    }
+}
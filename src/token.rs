@@ -0,0 +1,400 @@
+/*
+ * Copyright (C) 2023 debridge
+ *
+ * This file is part of debridge-solana-sdk.
+ *
+ * debridge-solana-sdk is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Lesser General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * debridge-solana-sdk is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Lesser General Public License for more details.
+ *
+ * You should have received a copy of the GNU Lesser General Public License
+ * along with debridge-solana-sdk. If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::{Clock, Epoch},
+    sysvar::Sysvar,
+};
+
+use crate::{Error, Pubkey, BPS_DENOMINATOR, SPL_TOKEN_2022_ID, SPL_TOKEN_ID};
+
+/// Mint authority is stored as `COption<Pubkey>` with 4-byte tag
+const MINT_AUTHORITY_OFFSET: usize = 4;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+/// Mints with extensions are padded to the length of token account
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const MINT_ACCOUNT_TYPE: u8 = 1;
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+/// Transfer fee config authority, withdraw withheld authority and withheld amount
+const OLDER_TRANSFER_FEE_OFFSET: usize = 72;
+const TRANSFER_FEE_LEN: usize = 18;
+
+/// Check that `token_program` is spl-token or Token-2022 program
+pub fn is_token_program_id(token_program: &Pubkey) -> bool {
+    token_program.eq(&SPL_TOKEN_ID) || token_program.eq(&SPL_TOKEN_2022_ID)
+}
+
+/// Get program owning the token mint. It's spl-token or Token-2022 program
+///
+/// # Arguments
+/// * `token_mint` - mint of sending tokens
+pub fn get_token_program_id(token_mint: &AccountInfo) -> Result<Pubkey, Error> {
+    if !is_token_program_id(token_mint.owner) {
+        return Err(Error::WrongTokenProgramId);
+    }
+
+    Ok(*token_mint.owner)
+}
+
+/// Get mint authority of spl-token or Token-2022 mint. `None` if supply of the mint is fixed
+pub fn get_mint_authority(token_mint: &AccountInfo) -> Result<Option<Pubkey>, Error> {
+    let data = token_mint
+        .try_borrow_data()
+        .map_err(|_| Error::AccountDeserializeError)?;
+    let authority = data
+        .get(MINT_AUTHORITY_OFFSET..MINT_AUTHORITY_OFFSET + 32)
+        .ok_or(Error::AccountDeserializeError)?;
+
+    match data[..MINT_AUTHORITY_OFFSET] {
+        [0, 0, 0, 0] => Ok(None),
+        [1, 0, 0, 0] => Ok(Some(Pubkey::new_from_array(
+            authority.try_into().expect("Unreachable"),
+        ))),
+        _ => Err(Error::AccountDeserializeError),
+    }
+}
+
+/// Get decimals of spl-token or Token-2022 mint
+pub fn get_mint_decimals(token_mint: &AccountInfo) -> Result<u8, Error> {
+    token_mint
+        .try_borrow_data()
+        .ok()
+        .and_then(|data| data.get(MINT_DECIMALS_OFFSET).copied())
+        .ok_or(Error::AccountDeserializeError)
+}
+
+/// Get amount of tokens in spl-token or Token-2022 token account
+///
+/// # Arguments
+/// * `token_account` - token account, e.g. send_from wallet
+pub fn get_token_account_amount(token_account: &AccountInfo) -> Result<u64, Error> {
+    token_account
+        .try_borrow_data()
+        .ok()
+        .and_then(|data| {
+            data.get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+                .map(|amount| u64::from_le_bytes(amount.try_into().expect("Unreachable")))
+        })
+        .ok_or(Error::AccountDeserializeError)
+}
+
+/// Fee of Token-2022 transfer fee extension for some epoch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFee {
+    /// First epoch where the transfer fee takes effect
+    pub epoch: Epoch,
+    /// Maximum fee assessed on transfers
+    pub maximum_fee: u64,
+    /// Amount of transfer collected as fees, expressed as basis points of the transfer amount
+    pub transfer_fee_basis_points: u16,
+}
+
+impl TransferFee {
+    fn try_from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let read_u64 = |offset: usize| {
+            data.get(offset..offset + 8)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("Unreachable")))
+                .ok_or(Error::AccountDeserializeError)
+        };
+
+        Ok(TransferFee {
+            epoch: read_u64(0)?,
+            maximum_fee: read_u64(8)?,
+            transfer_fee_basis_points: data
+                .get(16..18)
+                .map(|bytes| u16::from_le_bytes(bytes.try_into().expect("Unreachable")))
+                .ok_or(Error::AccountDeserializeError)?,
+        })
+    }
+
+    /// Calculate fee withheld from `amount` transfer. Rounded up as in Token-2022 program
+    pub fn calculate_fee(&self, amount: u64) -> Option<u64> {
+        if self.transfer_fee_basis_points == 0 || amount == 0 {
+            return Some(0);
+        }
+
+        let fee = u128::from(amount)
+            .checked_mul(u128::from(self.transfer_fee_basis_points))?
+            .checked_add(u128::from(BPS_DENOMINATOR) - 1)?
+            .checked_div(u128::from(BPS_DENOMINATOR))?;
+
+        Some(u64::try_from(fee).ok()?.min(self.maximum_fee))
+    }
+
+    /// Calculate transfer amount so that `post_fee_amount` is received after fee withholding
+    pub fn calculate_pre_fee_amount(&self, post_fee_amount: u64) -> Option<u64> {
+        let transfer_fee_bps = u128::from(self.transfer_fee_basis_points);
+        let denominator = u128::from(BPS_DENOMINATOR);
+
+        match (transfer_fee_bps, post_fee_amount) {
+            (0, _) => Some(post_fee_amount),
+            (_, 0) => Some(0),
+            (bps, _) if bps == denominator => post_fee_amount.checked_add(self.maximum_fee),
+            (bps, _) => {
+                let raw_pre_fee_amount = u128::from(post_fee_amount)
+                    .checked_mul(denominator)?
+                    .checked_add(denominator - bps - 1)?
+                    .checked_div(denominator.checked_sub(bps)?)?;
+
+                if raw_pre_fee_amount - u128::from(post_fee_amount) >= u128::from(self.maximum_fee)
+                {
+                    post_fee_amount.checked_add(self.maximum_fee)
+                } else {
+                    u64::try_from(raw_pre_fee_amount).ok()
+                }
+            }
+        }
+    }
+}
+
+/// Token-2022 transfer fee extension of mint
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    /// Transfer fee in effect before `newer_transfer_fee.epoch`
+    pub older_transfer_fee: TransferFee,
+    /// Transfer fee in effect starting from its `epoch`
+    pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Get transfer fee config from Token-2022 mint data. `None` if mint has no transfer fee extension
+    pub fn try_from_mint_data(data: &[u8]) -> Result<Option<Self>, Error> {
+        if data.len() <= ACCOUNT_TYPE_OFFSET {
+            return Ok(None);
+        }
+        if data[ACCOUNT_TYPE_OFFSET] != MINT_ACCOUNT_TYPE {
+            return Err(Error::AccountDeserializeError);
+        }
+
+        let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+        while let Some(header) = data.get(offset..offset + 4) {
+            let extension_type = u16::from_le_bytes([header[0], header[1]]);
+            let len = usize::from(u16::from_le_bytes([header[2], header[3]]));
+            let value = data
+                .get(offset + 4..offset + 4 + len)
+                .ok_or(Error::AccountDeserializeError)?;
+
+            if extension_type == TRANSFER_FEE_CONFIG_EXTENSION {
+                let older_transfer_fee = value
+                    .get(OLDER_TRANSFER_FEE_OFFSET..)
+                    .ok_or(Error::AccountDeserializeError)
+                    .and_then(TransferFee::try_from_bytes)?;
+                let newer_transfer_fee = value
+                    .get(OLDER_TRANSFER_FEE_OFFSET + TRANSFER_FEE_LEN..)
+                    .ok_or(Error::AccountDeserializeError)
+                    .and_then(TransferFee::try_from_bytes)?;
+
+                return Ok(Some(TransferFeeConfig {
+                    older_transfer_fee,
+                    newer_transfer_fee,
+                }));
+            }
+
+            offset += 4 + len;
+        }
+
+        Ok(None)
+    }
+
+    /// Get transfer fee in effect at `epoch`
+    pub fn get_epoch_fee(&self, epoch: Epoch) -> &TransferFee {
+        if epoch >= self.newer_transfer_fee.epoch {
+            &self.newer_transfer_fee
+        } else {
+            &self.older_transfer_fee
+        }
+    }
+
+    /// Get transfer fee in effect at current epoch. Clock is requested
+    /// only if the fee is changing at some epoch
+    pub fn get_current_fee(&self) -> Result<&TransferFee, Error> {
+        let is_fee_changing = self.older_transfer_fee.maximum_fee
+            != self.newer_transfer_fee.maximum_fee
+            || self.older_transfer_fee.transfer_fee_basis_points
+                != self.newer_transfer_fee.transfer_fee_basis_points;
+
+        if !is_fee_changing {
+            return Ok(&self.newer_transfer_fee);
+        }
+
+        let epoch = Clock::get().map_err(|_| Error::FailedToGetClock)?.epoch;
+        Ok(self.get_epoch_fee(epoch))
+    }
+}
+
+/// Get Token-2022 transfer fee config of mint. `None` for mints not owned by
+/// Token-2022 program and Token-2022 mints without transfer fee extension
+///
+/// # Arguments
+/// * `token_mint` - mint of sending tokens
+pub fn get_transfer_fee_config(
+    token_mint: &AccountInfo,
+) -> Result<Option<TransferFeeConfig>, Error> {
+    if token_mint.owner.ne(&SPL_TOKEN_2022_ID) {
+        return Ok(None);
+    }
+
+    TransferFeeConfig::try_from_mint_data(
+        &token_mint
+            .try_borrow_data()
+            .map_err(|_| Error::AccountDeserializeError)?,
+    )
+}
+
+/// Add Token-2022 transfer fee withheld when `post_fee_amount` is transferred.
+/// Amount is returned unchanged for mints without transfer fee
+///
+/// # Arguments
+/// * `token_mint` - mint of sending tokens
+/// * `post_fee_amount` - amount that has to be received after transfer
+pub fn add_token_transfer_fee(
+    token_mint: &AccountInfo,
+    post_fee_amount: u64,
+) -> Result<u64, Error> {
+    match get_transfer_fee_config(token_mint)? {
+        Some(config) => config
+            .get_current_fee()?
+            .calculate_pre_fee_amount(post_fee_amount)
+            .ok_or(Error::AmountOverflowedWhileAddingFee),
+        None => Ok(post_fee_amount),
+    }
+}
+
+/// Subtract Token-2022 transfer fee withheld when `amount` is transferred.
+/// Inverse of [`add_token_transfer_fee`]
+///
+/// # Arguments
+/// * `token_mint` - mint of sending tokens
+/// * `amount` - amount that is transferred
+pub fn subtract_token_transfer_fee(token_mint: &AccountInfo, amount: u64) -> Result<u64, Error> {
+    match get_transfer_fee_config(token_mint)? {
+        Some(config) => config
+            .get_current_fee()?
+            .calculate_fee(amount)
+            .and_then(|fee| amount.checked_sub(fee))
+            .ok_or(Error::AmountTooSmallToCoverFees),
+        None => Ok(amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        token::{TransferFee, TransferFeeConfig},
+        Error,
+    };
+
+    fn transfer_fee_bytes(transfer_fee: &TransferFee) -> Vec<u8> {
+        [
+            transfer_fee.epoch.to_le_bytes().as_slice(),
+            &transfer_fee.maximum_fee.to_le_bytes(),
+            &transfer_fee.transfer_fee_basis_points.to_le_bytes(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_transfer_fee_config_from_mint_data() {
+        let older_transfer_fee = TransferFee {
+            epoch: 10,
+            maximum_fee: 1000,
+            transfer_fee_basis_points: 50,
+        };
+        let newer_transfer_fee = TransferFee {
+            epoch: 20,
+            maximum_fee: 2000,
+            transfer_fee_basis_points: 100,
+        };
+
+        let mut data = vec![0; 165];
+        assert_eq!(TransferFeeConfig::try_from_mint_data(&data[..82]), Ok(None));
+
+        data.push(1);
+        // Mint close authority extension goes before transfer fee config
+        data.extend(3_u16.to_le_bytes());
+        data.extend(32_u16.to_le_bytes());
+        data.extend([7; 32]);
+        assert_eq!(TransferFeeConfig::try_from_mint_data(&data), Ok(None));
+
+        data.extend(1_u16.to_le_bytes());
+        data.extend(108_u16.to_le_bytes());
+        data.extend([0; 72]);
+        data.extend(transfer_fee_bytes(&older_transfer_fee));
+        data.extend(transfer_fee_bytes(&newer_transfer_fee));
+
+        let config = TransferFeeConfig::try_from_mint_data(&data)
+            .expect("Failed to parse mint")
+            .expect("Transfer fee config not found");
+        assert_eq!(config.older_transfer_fee, older_transfer_fee);
+        assert_eq!(config.newer_transfer_fee, newer_transfer_fee);
+        assert_eq!(config.get_epoch_fee(19), &older_transfer_fee);
+        assert_eq!(config.get_epoch_fee(20), &newer_transfer_fee);
+
+        assert_eq!(
+            TransferFeeConfig::try_from_mint_data(&data[..data.len() - 1]),
+            Err(Error::AccountDeserializeError)
+        );
+    }
+
+    #[test]
+    fn test_transfer_fee() {
+        let transfer_fee = TransferFee {
+            epoch: 0,
+            maximum_fee: 5,
+            transfer_fee_basis_points: 100,
+        };
+
+        assert_eq!(transfer_fee.calculate_fee(0), Some(0));
+        assert_eq!(transfer_fee.calculate_fee(101), Some(2));
+        assert_eq!(transfer_fee.calculate_fee(10000), Some(5));
+        assert_eq!(transfer_fee.calculate_pre_fee_amount(0), Some(0));
+        assert_eq!(transfer_fee.calculate_pre_fee_amount(99), Some(100));
+        assert_eq!(transfer_fee.calculate_pre_fee_amount(10000), Some(10005));
+
+        let full_fee = TransferFee {
+            transfer_fee_basis_points: 10000,
+            ..transfer_fee
+        };
+        assert_eq!(full_fee.calculate_pre_fee_amount(100), Some(105));
+    }
+
+    proptest! {
+        #[test]
+        fn test_pre_fee_amount_covers_fee(
+            post_fee_amount in 0..=u64::MAX / 2,
+            maximum_fee in 0..=u64::MAX / 2,
+            transfer_fee_basis_points in 0..10000_u16,
+        ) {
+            let transfer_fee = TransferFee {
+                epoch: 0,
+                maximum_fee,
+                transfer_fee_basis_points,
+            };
+
+            let pre_fee_amount = transfer_fee.calculate_pre_fee_amount(post_fee_amount).unwrap();
+            let fee = transfer_fee.calculate_fee(pre_fee_amount).unwrap();
+
+            prop_assert!(pre_fee_amount - fee >= post_fee_amount);
+        }
+    }
+}